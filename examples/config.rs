@@ -14,7 +14,7 @@ fn main() {
 
     //A view with a single config change, everything else default, chainable version
     let config = HexViewConfig {
-        hex_ascii_separator: " - ",
+        hex_ascii_separator: " - ".into(),
         ..Default::default()
     };
     let view2 = HexView::new_from_iter(data)
@@ -26,7 +26,7 @@ fn main() {
     let config2 = HexViewConfig {
         bytes_per_line: 8,
         bytes_per_group: 4,
-        byte_group_separator: "     ",
+        byte_group_separator: "     ".into(),
         show_ascii: false,
         ..Default::default()
     };
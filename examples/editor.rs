@@ -0,0 +1,22 @@
+extern crate cursive;
+extern crate cursive_hexview;
+
+use cursive::event::Key;
+use cursive::view::Resizable;
+use cursive_hexview::{DisplayState, HexEditorView, HexView};
+
+fn main() {
+    let mut cur = cursive::default();
+    let view = HexView::new_from_iter(b"Hello, World!").display_state(DisplayState::Editable);
+    let mut editor = HexEditorView::new(view);
+    if let Some(path) = std::env::args().nth(1) {
+        if let Err(e) = editor.open(&path) {
+            eprintln!("Could not open {path}: {e}");
+            return;
+        }
+    }
+
+    cur.add_fullscreen_layer(editor.full_screen());
+    cur.add_global_callback(Key::Esc, |cur| cur.quit());
+    cur.run();
+}
@@ -0,0 +1,38 @@
+extern crate cursive;
+extern crate cursive_hexview;
+
+use std::thread;
+use std::time::Duration;
+
+use cursive::event::Key;
+use cursive::views::Dialog;
+use cursive_hexview::{DisplayState, HexView, HexViewConfig, Region, SimulatedSource};
+
+fn main() {
+    let device = SimulatedSource::with_data(0x2000_0000, &[0; 256]);
+    device.unmap(0x2000_0040..0x2000_0050);
+
+    // a counter which the device increments in the background
+    let counter = device.clone();
+    thread::spawn(move || {
+        for i in 0u32.. {
+            counter.store(0x2000_0000 + u64::from(i % 8) * 4, &i.to_le_bytes());
+            thread::sleep(Duration::from_millis(300));
+        }
+    });
+
+    let view = HexView::new_from_regions(vec![Region::new("ram", 0, vec![0; 256])])
+        .expect("a single region is valid")
+        .with_config(HexViewConfig {
+            start_addr: 0x2000_0000,
+            ..Default::default()
+        })
+        .display_state(DisplayState::Enabled)
+        .with_source(device);
+
+    let mut cur = cursive::default();
+    cur.set_fps(10);
+    cur.add_layer(Dialog::around(view).title("Live memory"));
+    cur.add_global_callback(Key::Esc, |cur| cur.quit());
+    cur.run();
+}
@@ -0,0 +1,188 @@
+extern crate cursive;
+extern crate cursive_hexview;
+
+use cursive::event::{Event, Key};
+use cursive::view::{Nameable, Resizable};
+use cursive::views::Dialog;
+use cursive::Cursive;
+#[cfg(target_os = "linux")]
+use cursive_hexview::ProcessMemory;
+use cursive_hexview::{DisplayState, DumpFormat, HexEditorView, HexView, HexViewConfig};
+use std::convert::TryFrom;
+use std::env;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::process;
+
+const USAGE: &str = "\
+Usage: hexview [OPTIONS] [FILE]
+
+Views and edits FILE, or the data read from stdin if FILE is missing or `-`.
+With --pid, views and edits the memory of a running process instead.
+
+Options:
+      --cols <N>       Number of bytes per line [default: 16]
+      --group <N>      Number of bytes per group [default: 1]
+      --start <ADDR>   Address of the first byte, decimal or hex with a 0x prefix [default: 0]
+      --no-ascii       Hide the text column
+      --readonly       Do not allow to edit or save the data
+      --dump           Print a hex dump to stdout instead of starting the editor
+      --pid <PID>      View the memory of the process PID (Linux only)
+  -h, --help           Print this help";
+
+/// The name of the editor view in the cursive tree
+const EDITOR: &str = "editor";
+
+/// The parsed command line
+#[derive(Debug, Default)]
+struct Options {
+    config: HexViewConfig,
+    readonly: bool,
+    dump: bool,
+    pid: Option<u32>,
+    file: Option<String>,
+}
+
+fn parse_number(option: &str, value: Option<String>) -> Result<u64, String> {
+    let value = value.ok_or_else(|| format!("{option} needs a value"))?;
+    let parsed = match value.strip_prefix("0x").or_else(|| value.strip_prefix("0X")) {
+        Some(hex) => u64::from_str_radix(hex, 16),
+        None => value.parse(),
+    };
+    parsed.map_err(|_| format!("invalid value {value:?} for {option}"))
+}
+
+fn parse_args<I: Iterator<Item = String>>(mut args: I) -> Result<Option<Options>, String> {
+    let mut options = Options::default();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "--cols" => options.config.bytes_per_line = parse_number(&arg, args.next())? as usize,
+            "--group" => options.config.bytes_per_group = parse_number(&arg, args.next())? as usize,
+            "--start" => options.config.start_addr = parse_number(&arg, args.next())?,
+            "--no-ascii" => options.config.show_ascii = false,
+            "--readonly" => options.readonly = true,
+            "--dump" => options.dump = true,
+            "--pid" => {
+                let pid = parse_number(&arg, args.next())?;
+                options.pid = Some(u32::try_from(pid).map_err(|_| format!("invalid process id {pid}"))?);
+            }
+            _ if arg.starts_with('-') && arg != "-" => return Err(format!("unknown option {arg}")),
+            _ if options.file.is_some() => return Err(format!("unexpected argument {arg}")),
+            _ => options.file = Some(arg),
+        }
+    }
+    if options.pid.is_some() && (options.dump || options.file.is_some()) {
+        return Err("--pid cannot be combined with --dump or FILE".to_owned());
+    }
+    options.config.validate().map_err(|e| e.to_string())?;
+    Ok(Some(options))
+}
+
+/// Reads the file, or stdin for `None` and `-`
+fn read_input(file: Option<&str>) -> io::Result<Vec<u8>> {
+    match file {
+        Some(path) if path != "-" => fs::read(path),
+        _ => {
+            let mut data = Vec::new();
+            io::stdin().read_to_end(&mut data)?;
+            Ok(data)
+        }
+    }
+}
+
+/// Quits, after asking whether unsaved edits should be discarded
+///
+/// Edits of a process are written back right away, so there is nothing to ask for.
+fn quit(s: &mut Cursive) {
+    let dirty = s
+        .call_on_name(EDITOR, |e: &mut HexEditorView| {
+            e.hex_view().is_dirty() && !e.hex_view().has_source()
+        })
+        .unwrap_or(false);
+    if dirty {
+        s.add_layer(
+            Dialog::text("There are unsaved changes. Quit anyway?")
+                .button("Quit", Cursive::quit)
+                .dismiss_button("Cancel"),
+        );
+    } else {
+        s.quit();
+    }
+}
+
+/// Creates a view of the memory of the process `pid`, which keeps the configured layout
+#[cfg(target_os = "linux")]
+fn process_view(pid: u32, config: HexViewConfig) -> Result<HexView, String> {
+    let mut view = ProcessMemory::open(pid)
+        .and_then(ProcessMemory::into_view)
+        .map_err(|e| format!("cannot open the memory of process {pid}: {e}"))?;
+    let start_addr = view.config().start_addr;
+    view.set_config(HexViewConfig { start_addr, ..config });
+    Ok(view)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn process_view(_pid: u32, _config: HexViewConfig) -> Result<HexView, String> {
+    Err("--pid is only supported on Linux".to_owned())
+}
+
+fn run(options: Options) -> Result<(), String> {
+    let view = match options.pid {
+        Some(pid) => process_view(pid, options.config)?,
+        None => {
+            let data = read_input(options.file.as_deref()).map_err(|e| format!("cannot read input: {e}"))?;
+            HexView::new_from_iter(data).with_config(options.config)
+        }
+    };
+
+    if options.dump {
+        let format = DumpFormat::from_config(view.config());
+        let stdout = io::stdout();
+        let mut out = stdout.lock();
+        return view
+            .write_dump(&format, &mut out)
+            .and_then(|()| out.flush())
+            .map_err(|e| format!("cannot write dump: {e}"));
+    }
+
+    let state = if options.readonly {
+        DisplayState::Enabled
+    } else {
+        DisplayState::Editable
+    };
+    let file_commands = !options.readonly && options.pid.is_none();
+    let mut editor = HexEditorView::new(view.display_state(state)).file_commands(file_commands);
+    if let Some(path) = options.file.filter(|path| path != "-") {
+        editor.set_path(path);
+    }
+
+    let mut cur = cursive::default();
+    if options.pid.is_some() {
+        // lets the view poll the process
+        cur.set_fps(4);
+    }
+    cur.add_fullscreen_layer(editor.with_name(EDITOR).full_screen());
+    cur.add_global_callback(Key::Esc, quit);
+    cur.add_global_callback(Event::CtrlChar('q'), quit);
+    cur.run();
+    Ok(())
+}
+
+fn main() {
+    let options = match parse_args(env::args().skip(1)) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            println!("{USAGE}");
+            return;
+        }
+        Err(e) => {
+            eprintln!("hexview: {e}\n\n{USAGE}");
+            process::exit(2);
+        }
+    };
+    if let Err(e) = run(options) {
+        eprintln!("hexview: {e}");
+        process::exit(1);
+    }
+}
@@ -0,0 +1,269 @@
+use HexView;
+
+/// The maximum number of positions the jump list remembers in each direction
+const MAX_JUMPS: usize = 100;
+
+/// The positions the cursor jumped away from, like the jump list of vim
+#[derive(Debug, Clone, Default)]
+pub(crate) struct JumpList {
+    back: Vec<u64>,
+    forward: Vec<u64>,
+}
+
+impl JumpList {
+    /// Records a jump away from `addr`, which makes the positions jumped back from unreachable
+    fn push(&mut self, addr: u64) {
+        if self.back.last() != Some(&addr) {
+            push_bounded(&mut self.back, addr);
+        }
+        self.forward.clear();
+    }
+
+    /// Returns the position to go back to from `addr`
+    fn back(&mut self, addr: u64) -> Option<u64> {
+        let target = self.back.pop()?;
+        push_bounded(&mut self.forward, addr);
+        Some(target)
+    }
+
+    /// Returns the position to go forward to from `addr`
+    fn forward(&mut self, addr: u64) -> Option<u64> {
+        let target = self.forward.pop()?;
+        push_bounded(&mut self.back, addr);
+        Some(target)
+    }
+}
+
+/// Pushes `addr` onto `stack`, dropping the oldest entry if it is full
+fn push_bounded(stack: &mut Vec<u64>, addr: u64) {
+    if stack.len() == MAX_JUMPS {
+        stack.remove(0);
+    }
+    stack.push(addr);
+}
+
+// implements bookmarks and the jump list
+impl HexView {
+    /// Returns all bookmarks with their addresses, sorted by name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cursive_hexview::HexView;
+    /// let mut view = HexView::new_from_iter(vec![0; 64]);
+    /// view.add_bookmark('b', 0x20);
+    /// view.add_bookmark('a', 0x10);
+    /// assert_eq!(view.bookmarks().collect::<Vec<_>>(), vec![('a', 0x10), ('b', 0x20)]);
+    ///
+    /// assert_eq!(view.remove_bookmark('a'), Some(0x10));
+    /// assert_eq!(view.bookmark('a'), None);
+    /// ```
+    pub fn bookmarks(&self) -> impl Iterator<Item = (char, u64)> + '_ {
+        self.bookmarks.iter().map(|(&name, &addr)| (name, addr))
+    }
+
+    /// Returns the address of the bookmark `name`.
+    #[must_use]
+    pub fn bookmark(&self, name: char) -> Option<u64> {
+        self.bookmarks.get(&name).copied()
+    }
+
+    /// Sets the bookmark `name` to `addr`, replacing a bookmark with the same name.
+    ///
+    /// Bookmarked lines are marked with the name of the bookmark in front of their address. With the default
+    /// keymap, <kbd>m</kbd> followed by a letter sets a bookmark at the cursor and <kbd>'</kbd> followed by the
+    /// letter jumps back to it.
+    ///
+    /// Names are ASCII letters, so they fit into the marker column. Returns `false` for any other name, no
+    /// bookmark is set in this case.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cursive_hexview::HexView;
+    /// let mut view = HexView::new_from_iter(vec![0; 64]);
+    /// assert!(view.add_bookmark('Q', 0x10));
+    /// assert!(!view.add_bookmark('1', 0x20));
+    /// assert_eq!(view.bookmark('Q'), Some(0x10));
+    /// ```
+    pub fn add_bookmark(&mut self, name: char, addr: u64) -> bool {
+        if !name.is_ascii_alphabetic() {
+            return false;
+        }
+        self.bookmarks.insert(name, addr);
+        self.layout_changed = true;
+        true
+    }
+
+    /// Removes the bookmark `name` and returns its address.
+    pub fn remove_bookmark(&mut self, name: char) -> Option<u64> {
+        let addr = self.bookmarks.remove(&name);
+        if addr.is_some() {
+            self.layout_changed = true;
+        }
+        addr
+    }
+
+    /// Removes all bookmarks.
+    pub fn clear_bookmarks(&mut self) {
+        if !self.bookmarks.is_empty() {
+            self.bookmarks.clear();
+            self.layout_changed = true;
+        }
+    }
+
+    /// Moves the cursor to the bookmark `name`, recording the jump. Returns `false` if there is no such bookmark.
+    pub fn jump_to_bookmark(&mut self, name: char) -> bool {
+        match self.bookmark(name) {
+            Some(addr) => {
+                self.jump_to(addr);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Moves the cursor to `addr` like [`set_cursor_addr`](#method.set_cursor_addr), but remembers the old
+    /// position in the jump list.
+    ///
+    /// Going to an address, searching, jumping to a bookmark and following a pointer all record their jumps, so
+    /// [`jump_back`](#method.jump_back) and [`jump_forward`](#method.jump_forward) can walk through them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cursive_hexview::HexView;
+    /// let mut view = HexView::new_from_iter(vec![0; 64]);
+    /// view.jump_to(0x10);
+    /// view.jump_to(0x20);
+    ///
+    /// assert!(view.jump_back());
+    /// assert_eq!(view.cursor_addr(), 0x10);
+    /// assert!(view.jump_back());
+    /// assert_eq!(view.cursor_addr(), 0);
+    /// assert!(!view.jump_back());
+    ///
+    /// assert!(view.jump_forward());
+    /// assert_eq!(view.cursor_addr(), 0x10);
+    /// ```
+    pub fn jump_to(&mut self, addr: u64) {
+        self.record_jump();
+        self.set_cursor_addr(addr);
+    }
+
+    /// Moves the cursor back to the position of the last recorded jump. Returns `false` if there is none.
+    pub fn jump_back(&mut self) -> bool {
+        let current = self.cursor_addr();
+        match self.jumps.back(current) {
+            Some(addr) => {
+                self.set_cursor_addr(addr);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Reverts the last [`jump_back`](#method.jump_back). Returns `false` if there is nothing to revert.
+    pub fn jump_forward(&mut self) -> bool {
+        let current = self.cursor_addr();
+        match self.jumps.forward(current) {
+            Some(addr) => {
+                self.set_cursor_addr(addr);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Remembers the cursor position before a jump
+    pub(crate) fn record_jump(&mut self) {
+        let current = self.cursor_addr();
+        self.jumps.push(current);
+    }
+
+    /// Returns the width of the bookmark markers in front of the addresses
+    pub(crate) fn get_gutter_width(&self) -> usize {
+        usize::from(!self.bookmarks.is_empty())
+    }
+
+    /// Returns the name of the first bookmark which points into `line`
+    pub(crate) fn get_bookmark_in_line(&self, line: u64) -> Option<char> {
+        let bytes_per_line = self.bytes_per_line() as u64;
+        self.bookmarks
+            .iter()
+            .find(|(_, &addr)| addr.wrapping_sub(self.config.start_addr) / bytes_per_line == line)
+            .map(|(&name, _)| name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cursive::event::Event;
+    use cursive::view::View;
+
+    use super::*;
+    use DisplayState;
+
+    #[test]
+    fn invalid_bookmark_names() {
+        let mut view = HexView::new_from_iter(vec![0; 64]).display_state(DisplayState::Editable);
+        assert!(!view.add_bookmark('字', 0x20));
+        assert!(!view.add_bookmark(' ', 0x20));
+
+        // an invalid name typed after `m` cancels it, instead of editing the data
+        view.on_event(Event::Char('m'));
+        view.on_event(Event::Char('1'));
+        assert_eq!(view.bookmarks().count(), 0);
+        assert_eq!(view.byte(0), Some(0));
+
+        view.on_event(Event::Char('m'));
+        view.on_event(Event::Char('z'));
+        assert_eq!(view.bookmark('z'), Some(0));
+    }
+
+    #[test]
+    fn relayout_only_when_markers_change() {
+        let mut view = HexView::new_from_iter(vec![0; 64]);
+        view.layout((80, 4).into());
+        assert_eq!(view.remove_bookmark('a'), None);
+        view.clear_bookmarks();
+        assert!(!view.needs_relayout());
+
+        view.add_bookmark('a', 0x10);
+        assert!(view.needs_relayout());
+        assert_eq!(view.get_gutter_width(), 1);
+        assert_eq!(view.get_bookmark_in_line(1), Some('a'));
+        view.layout((80, 4).into());
+        view.clear_bookmarks();
+        assert!(view.needs_relayout());
+        assert_eq!(view.get_gutter_width(), 0);
+    }
+
+    #[test]
+    fn jump_list() {
+        let mut jumps = JumpList::default();
+        jumps.push(1);
+        jumps.push(1);
+        jumps.push(2);
+        assert_eq!(jumps.back(3), Some(2));
+        assert_eq!(jumps.back(2), Some(1));
+        assert_eq!(jumps.back(1), None);
+        assert_eq!(jumps.forward(1), Some(2));
+
+        // a new jump drops the positions jumped back from
+        jumps.push(2);
+        assert_eq!(jumps.forward(2), None);
+        assert_eq!(jumps.back(5), Some(2));
+        assert_eq!(jumps.back(2), Some(1));
+    }
+
+    #[test]
+    fn jump_list_is_bounded() {
+        let mut jumps = JumpList::default();
+        for addr in 0..MAX_JUMPS as u64 + 10 {
+            jumps.push(addr);
+        }
+        assert_eq!(jumps.back.len(), MAX_JUMPS);
+        assert_eq!(jumps.back[0], 10);
+    }
+}
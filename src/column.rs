@@ -0,0 +1,145 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::sync::Arc;
+
+use cursive::theme::ColorStyle;
+
+/// Draws a user defined column of a [`HexView`].
+///
+/// [`HexView`]: struct.HexView.html
+///
+/// # Examples
+///
+/// A column which shows the sum of all bytes in a line:
+///
+/// ```
+/// # use cursive_hexview::{ColumnKind, ColumnRenderer, HexView, HexViewConfig};
+/// struct Checksum;
+///
+/// impl ColumnRenderer for Checksum {
+///     fn width(&self, _bytes_per_line: usize) -> usize {
+///         2
+///     }
+///
+///     fn render(&self, _addr: u64, bytes: &[Option<u8>]) -> String {
+///         let sum = bytes.iter().flatten().fold(0u8, |acc, b| acc.wrapping_add(*b));
+///         format!("{sum:02X}")
+///     }
+/// }
+///
+/// let mut config = HexViewConfig::default();
+/// config.columns.push(ColumnKind::custom(Checksum).into());
+/// let view = HexView::new_from_iter(b"ABC").with_config(config);
+/// ```
+pub trait ColumnRenderer: Send + Sync {
+    /// Returns the number of cells this column occupies, if each line holds `bytes_per_line` bytes.
+    fn width(&self, bytes_per_line: usize) -> usize;
+
+    /// Returns the text of the line which starts at `addr`.
+    ///
+    /// `bytes` holds one entry for every byte of the line, unmapped bytes are `None`. The text is cropped to
+    /// the [`width`](#tymethod.width) of the column.
+    fn render(&self, addr: u64, bytes: &[Option<u8>]) -> String;
+}
+
+/// Describes what a [`Column`] displays.
+///
+/// [`Column`]: struct.Column.html
+#[derive(Clone)]
+pub enum ColumnKind {
+    /// The address of the first byte of each line
+    Addr,
+    /// The bytes as hex digits
+    Hex,
+    /// The bytes as ASCII characters, non printable ones are displayed as `.`
+    Text,
+    /// A column drawn by a [`ColumnRenderer`](trait.ColumnRenderer.html)
+    Custom(Arc<dyn ColumnRenderer>),
+}
+
+impl ColumnKind {
+    /// Creates a column kind which is drawn by `renderer`.
+    pub fn custom<R: ColumnRenderer + 'static>(renderer: R) -> Self {
+        Self::Custom(Arc::new(renderer))
+    }
+}
+
+impl fmt::Debug for ColumnKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Addr => write!(f, "Addr"),
+            Self::Hex => write!(f, "Hex"),
+            Self::Text => write!(f, "Text"),
+            Self::Custom(_) => write!(f, "Custom(..)"),
+        }
+    }
+}
+
+/// A single column in the layout of a [`HexView`].
+///
+/// The columns are drawn in the order of [`HexViewConfig::columns`], disabled columns are skipped. Two adjacent
+/// columns are divided by a separator: either the [`separator`](#structfield.separator) of the right column or, if
+/// that is `None`, the [`addr_hex_separator`] after an address column and the [`hex_ascii_separator`] after any
+/// other column.
+///
+/// [`HexView`]: struct.HexView.html
+/// [`HexViewConfig::columns`]: struct.HexViewConfig.html#structfield.columns
+/// [`addr_hex_separator`]: struct.HexViewConfig.html#structfield.addr_hex_separator
+/// [`hex_ascii_separator`]: struct.HexViewConfig.html#structfield.hex_ascii_separator
+///
+/// # Examples
+///
+/// Hex only, drawn in a different color:
+///
+/// ```
+/// # extern crate cursive;
+/// # extern crate cursive_hexview;
+/// # use cursive::theme::ColorStyle;
+/// # use cursive_hexview::{Column, ColumnKind, HexView, HexViewConfig};
+/// # fn main() {
+/// let config = HexViewConfig {
+///     columns: vec![Column {
+///         style: Some(ColorStyle::secondary()),
+///         ..Column::new(ColumnKind::Hex)
+///     }],
+///     ..Default::default()
+/// };
+/// let view = HexView::new_from_iter(b"ABC").with_config(config);
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Column {
+    /// Controls what is displayed in this column.
+    pub kind: ColumnKind,
+    /// Controls if this column is displayed.
+    ///
+    /// Default is `true`
+    pub enabled: bool,
+    /// Controls the color of this column. `None` uses the color of the surrounding view.
+    ///
+    /// Default is `None`
+    pub style: Option<ColorStyle>,
+    /// Controls the separator in front of this column. `None` uses the one of the config.
+    ///
+    /// Default is `None`
+    pub separator: Option<Cow<'static, str>>,
+}
+
+impl Column {
+    /// Creates an enabled column of `kind` with the default style and separator.
+    #[must_use]
+    pub const fn new(kind: ColumnKind) -> Self {
+        Self {
+            kind,
+            enabled: true,
+            style: None,
+            separator: None,
+        }
+    }
+}
+
+impl From<ColumnKind> for Column {
+    fn from(kind: ColumnKind) -> Self {
+        Self::new(kind)
+    }
+}
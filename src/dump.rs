@@ -0,0 +1,640 @@
+use std::borrow::Cow;
+use std::fmt::{self, Write};
+use std::io;
+
+use unicode_width::UnicodeWidthStr;
+
+use parse::{build_view, Chunk, ParseError, ParseErrorKind};
+use region::{Row, RowMap};
+use {make_printable, validate_layout, ConfigError, HexView, HexViewConfig};
+
+/// Controls whether a dump ends with a line containing the address after the last byte.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EndAddress {
+    /// Never print the end address
+    Never,
+    /// Print the end address, unless there is no data at all
+    IfNotEmpty,
+    /// Always print the end address
+    Always,
+}
+
+/// Controls the layout of a textual dump, see [`HexView::dump`].
+///
+/// A dump line consists of the address, the hex representation of the bytes and, optionally, the ASCII
+/// representation. Besides the layout of the [`HexView`] itself ([`from_config`](#method.from_config)),
+/// there are presets which reproduce the output of common command line tools byte for byte.
+///
+/// Lines without any mapped byte are skipped, which is visible as a jump of the addresses. Unmapped and unknown
+/// bytes inside a line are written as `??` and as space in the ASCII representation.
+///
+/// [`HexView`]: struct.HexView.html
+/// [`HexView::dump`]: struct.HexView.html#method.dump
+#[derive(Debug, Clone)]
+pub struct DumpFormat {
+    /// Controls the number of bytes per line.
+    pub bytes_per_line: usize,
+    /// Controls the number of bytes per group.
+    pub bytes_per_group: usize,
+    /// Controls the separator between two bytes of the same group.
+    pub byte_separator: Cow<'static, str>,
+    /// Controls the separator between two groups.
+    pub byte_group_separator: Cow<'static, str>,
+    /// Controls the minimal number of digits of an address, `None` uses the width of the `HexView`.
+    pub addr_width: Option<usize>,
+    /// Controls the separator between the address and the hex representation.
+    pub addr_hex_separator: Cow<'static, str>,
+    /// Controls the separator between the hex and the ASCII representation.
+    pub hex_ascii_separator: Cow<'static, str>,
+    /// Controls the text in front of the ASCII representation.
+    pub ascii_prefix: Cow<'static, str>,
+    /// Controls the text after the ASCII representation.
+    pub ascii_suffix: Cow<'static, str>,
+    /// Controls if the ASCII representation should be written.
+    pub show_ascii: bool,
+    /// Controls if a space is written as is in the ASCII representation or as dot.
+    pub printable_space: bool,
+    /// Controls if addresses and hex values are written with upper case letters.
+    pub uppercase: bool,
+    /// Controls if a line which is equal to the previous one is replaced by a single `*`.
+    pub squeeze: bool,
+    /// Controls if the address after the last byte is written in an extra line.
+    pub end_address: EndAddress,
+}
+
+impl Default for DumpFormat {
+    fn default() -> Self {
+        Self::from_config(&HexViewConfig::default())
+    }
+}
+
+impl DumpFormat {
+    /// Creates the layout which is used by the `HexView` with `config`.
+    #[must_use]
+    pub fn from_config(config: &HexViewConfig) -> Self {
+        Self {
+            bytes_per_line: config.bytes_per_line,
+            bytes_per_group: config.bytes_per_group,
+            byte_separator: Cow::Borrowed(""),
+            byte_group_separator: config.byte_group_separator.clone(),
+            addr_width: None,
+            addr_hex_separator: config.addr_hex_separator.clone(),
+            hex_ascii_separator: config.hex_ascii_separator.clone(),
+            ascii_prefix: Cow::Borrowed(""),
+            ascii_suffix: Cow::Borrowed(""),
+            show_ascii: config.show_ascii,
+            printable_space: false,
+            uppercase: true,
+            squeeze: false,
+            end_address: EndAddress::Never,
+        }
+    }
+
+    /// Creates the layout of `hexdump -C`.
+    #[must_use]
+    pub const fn hexdump() -> Self {
+        Self {
+            bytes_per_line: 16,
+            bytes_per_group: 8,
+            byte_separator: Cow::Borrowed(" "),
+            byte_group_separator: Cow::Borrowed("  "),
+            addr_width: Some(8),
+            addr_hex_separator: Cow::Borrowed("  "),
+            hex_ascii_separator: Cow::Borrowed("  "),
+            ascii_prefix: Cow::Borrowed("|"),
+            ascii_suffix: Cow::Borrowed("|"),
+            show_ascii: true,
+            printable_space: true,
+            uppercase: false,
+            squeeze: true,
+            end_address: EndAddress::IfNotEmpty,
+        }
+    }
+
+    /// Creates the layout of `xxd`.
+    #[must_use]
+    pub const fn xxd() -> Self {
+        Self {
+            bytes_per_line: 16,
+            bytes_per_group: 2,
+            byte_separator: Cow::Borrowed(""),
+            byte_group_separator: Cow::Borrowed(" "),
+            addr_width: Some(8),
+            addr_hex_separator: Cow::Borrowed(": "),
+            hex_ascii_separator: Cow::Borrowed("  "),
+            ascii_prefix: Cow::Borrowed(""),
+            ascii_suffix: Cow::Borrowed(""),
+            show_ascii: true,
+            printable_space: true,
+            uppercase: false,
+            squeeze: false,
+            end_address: EndAddress::Never,
+        }
+    }
+
+    /// Creates the layout of `od -A x -t x1z`.
+    #[must_use]
+    pub const fn od() -> Self {
+        Self {
+            bytes_per_line: 16,
+            bytes_per_group: 1,
+            byte_separator: Cow::Borrowed(""),
+            byte_group_separator: Cow::Borrowed(" "),
+            addr_width: Some(6),
+            addr_hex_separator: Cow::Borrowed(" "),
+            hex_ascii_separator: Cow::Borrowed("  "),
+            ascii_prefix: Cow::Borrowed(">"),
+            ascii_suffix: Cow::Borrowed("<"),
+            show_ascii: true,
+            printable_space: true,
+            uppercase: false,
+            squeeze: true,
+            end_address: EndAddress::Always,
+        }
+    }
+
+    /// Checks whether the options of this format are consistent, see [`HexViewConfig::validate`].
+    ///
+    /// A dump with an invalid format clamps the offending values.
+    ///
+    /// [`HexViewConfig::validate`]: struct.HexViewConfig.html#method.validate
+    ///
+    /// # Errors
+    ///
+    /// Returns the first inconsistency found.
+    pub const fn validate(&self) -> Result<(), ConfigError> {
+        validate_layout(self.bytes_per_line, self.bytes_per_group)
+    }
+
+    /// returns the number of bytes per line, which is at least 1
+    fn bytes_per_line(&self) -> usize {
+        std::cmp::max(1, self.bytes_per_line)
+    }
+
+    /// returns the text which starts the ASCII representation, after the padding of the hex representation
+    fn ascii_marker(&self) -> String {
+        if self.show_ascii {
+            format!("{}{}", self.hex_ascii_separator.trim_start(), self.ascii_prefix)
+        } else {
+            String::new()
+        }
+    }
+
+    /// returns the separator in front of the byte at `index` in a line
+    fn separator(&self, index: usize) -> &str {
+        match index {
+            0 => "",
+            i if i % self.bytes_per_group.clamp(1, self.bytes_per_line()) == 0 => &self.byte_group_separator,
+            _ => &self.byte_separator,
+        }
+    }
+
+    /// returns the width of the hex representation of a complete line
+    fn hex_width(&self) -> usize {
+        (0..self.bytes_per_line()).map(|i| self.separator(i).width() + 2).sum()
+    }
+
+    fn write_addr<W: Write>(&self, f: &mut W, addr: u64, width: usize) -> fmt::Result {
+        if self.uppercase {
+            write!(f, "{addr:0width$X}")
+        } else {
+            write!(f, "{addr:0width$x}")
+        }
+    }
+
+    fn write_line<W: Write>(&self, f: &mut W, addr: u64, bytes: &[Option<u8>], addr_width: usize) -> fmt::Result {
+        self.write_addr(f, addr, addr_width)?;
+        f.write_str(&self.addr_hex_separator)?;
+
+        let mut hex = String::new();
+        for (i, b) in bytes.iter().enumerate() {
+            hex.push_str(self.separator(i));
+            match (b, self.uppercase) {
+                (Some(b), true) => write!(hex, "{b:02X}")?,
+                (Some(b), false) => write!(hex, "{b:02x}")?,
+                (None, _) => hex.push_str("??"),
+            }
+        }
+
+        if self.show_ascii {
+            let ascii: String = bytes
+                .iter()
+                .map(|b| match b {
+                    Some(b' ') if self.printable_space => ' ',
+                    Some(b) => make_printable(b),
+                    None => ' ',
+                })
+                .collect();
+            let padding = self.hex_width().saturating_sub(hex.width());
+            write!(
+                f,
+                "{hex}{:padding$}{}{}{ascii}{}",
+                "", self.hex_ascii_separator, self.ascii_prefix, self.ascii_suffix
+            )?;
+        } else {
+            f.write_str(&hex)?;
+        }
+        f.write_char('\n')
+    }
+}
+
+/// A parsed line of a dump
+enum DumpLine {
+    /// A `*`, which repeats the previous line up to the next address
+    Squeeze,
+    /// An address without data, which marks the end of the dump
+    End(u64),
+    /// An address with the bytes of this line, `None` for unknown bytes
+    Data(u64, Vec<Option<u8>>),
+}
+
+/// Parses line number `line` of a dump in `format`
+fn parse_dump_line(format: &DumpFormat, line: usize, text: &str) -> Result<DumpLine, ParseError> {
+    let trimmed = text.trim_start();
+    let error = |rest: &str, kind| ParseError::new(line, text.len() - rest.len() + 1, kind);
+    let unexpected = |rest: &str| {
+        error(
+            rest,
+            rest.chars()
+                .next()
+                .map_or(ParseErrorKind::UnexpectedEndOfLine, ParseErrorKind::UnexpectedCharacter),
+        )
+    };
+
+    if trimmed.trim_end() == "*" {
+        return Ok(DumpLine::Squeeze);
+    }
+
+    let digits = trimmed.find(|c: char| !c.is_ascii_hexdigit()).unwrap_or(trimmed.len());
+    let addr = u64::from_str_radix(&trimmed[..digits], 16).map_err(|_| match trimmed.chars().next() {
+        Some(c) if !c.is_ascii_hexdigit() => error(trimmed, ParseErrorKind::InvalidHexDigit(c)),
+        _ => error(trimmed, ParseErrorKind::InvalidAddress),
+    })?;
+    let mut rest = &trimmed[digits..];
+    if rest.trim_end().is_empty() {
+        return Ok(DumpLine::End(addr));
+    }
+
+    rest = rest
+        .strip_prefix(&*format.addr_hex_separator)
+        .ok_or_else(|| unexpected(rest))?;
+    let mut bytes = Vec::with_capacity(format.bytes_per_line());
+    for i in 0..format.bytes_per_line() {
+        let Some(hex) = rest.strip_prefix(format.separator(i)) else {
+            break;
+        };
+        // the hex representation of a short line is padded with whitespace
+        if hex.is_empty() || hex.starts_with(char::is_whitespace) {
+            break;
+        }
+        if hex.starts_with("??") {
+            bytes.push(None);
+        } else {
+            let mut digits = hex.chars().take(2).map(|c| c.to_digit(16).ok_or(c));
+            match (digits.next(), digits.next()) {
+                (Some(Ok(high)), Some(Ok(low))) => bytes.push(Some((high << 4 | low) as u8)),
+                (Some(Ok(_)), None) => return Err(error(&hex[1..], ParseErrorKind::UnexpectedEndOfLine)),
+                (Some(Ok(_)), Some(Err(c))) => return Err(error(&hex[1..], ParseErrorKind::InvalidHexDigit(c))),
+                (_, _) => break,
+            }
+        }
+        rest = &hex[2..];
+    }
+
+    // everything after the hex representation is either padding or the ASCII representation
+    let marker = format.ascii_marker();
+    let ascii = rest.trim_start();
+    let valid = if marker.is_empty() {
+        ascii.is_empty() || rest.starts_with(&*format.hex_ascii_separator)
+    } else {
+        ascii.starts_with(&marker)
+    };
+    if !valid || (!format.show_ascii && !ascii.is_empty()) {
+        return Err(unexpected(ascii));
+    }
+
+    Ok(DumpLine::Data(addr, bytes))
+}
+
+/// Parses a complete dump in `format`
+fn parse_dump(format: &DumpFormat, text: &str) -> Result<HexView, ParseError> {
+    let bpl = format.bytes_per_line() as u64;
+    let mut chunks = Vec::new();
+    // the previous data line as (line number, address, bytes)
+    let mut prev: Option<(usize, u64, Vec<Option<u8>>)> = None;
+    let mut squeeze = None;
+
+    let mut push = |line, addr: u64, bytes: &[Option<u8>]| {
+        let mut offset = 0;
+        for run in bytes.split(Option::is_none) {
+            chunks.push(Chunk {
+                addr: addr + offset,
+                line,
+                data: run.iter().flatten().copied().collect(),
+            });
+            offset += run.len() as u64 + 1;
+        }
+    };
+
+    for (idx, text) in text.lines().enumerate() {
+        let line = idx + 1;
+        if text.trim().is_empty() {
+            continue;
+        }
+
+        let parsed = parse_dump_line(format, line, text)?;
+        let addr = match parsed {
+            DumpLine::Squeeze if prev.is_some() => {
+                squeeze = Some(line);
+                continue;
+            }
+            DumpLine::Squeeze => return Err(ParseError::new(line, 1, ParseErrorKind::UnexpectedCharacter('*'))),
+            DumpLine::End(addr) | DumpLine::Data(addr, _) => addr,
+        };
+
+        // a squeeze repeats the previous line up to the current address
+        if let (Some(squeeze_line), Some((_, prev_addr, prev_bytes))) = (squeeze.take(), &prev) {
+            let mut fill = prev_addr.saturating_add(bpl);
+            while fill < addr {
+                push(squeeze_line, fill, prev_bytes);
+                fill = fill.saturating_add(bpl);
+            }
+        } else if let (DumpLine::Data(..), Some((prev_line, prev_addr, prev_bytes))) = (&parsed, &prev) {
+            // only the last line before a gap may be shorter than a complete line
+            if prev_bytes.len() < format.bytes_per_line() && prev_addr.saturating_add(prev_bytes.len() as u64) == addr {
+                return Err(ParseError::new(
+                    *prev_line,
+                    1,
+                    ParseErrorKind::LengthMismatch {
+                        expected: format.bytes_per_line(),
+                        found: prev_bytes.len(),
+                    },
+                ));
+            }
+        }
+
+        if let DumpLine::Data(addr, bytes) = parsed {
+            push(line, addr, &bytes);
+            prev = Some((line, addr, bytes));
+        }
+    }
+
+    build_view(chunks)
+}
+
+/// A textual dump of the data of a [`HexView`], created by [`HexView::dump`].
+///
+/// [`HexView`]: struct.HexView.html
+/// [`HexView::dump`]: struct.HexView.html#method.dump
+#[derive(Debug, Clone, Copy)]
+pub struct Dump<'a> {
+    view: &'a HexView,
+    format: &'a DumpFormat,
+}
+
+impl fmt::Display for Dump<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Dump { view, format } = *self;
+        let bpl = format.bytes_per_line();
+        let addr_width = format.addr_width.unwrap_or_else(|| view.get_addr_digit_length());
+        let rows = RowMap::new(view.regions(), bpl);
+
+        let mut prev: Option<(u64, Vec<Option<u8>>)> = None;
+        let mut squeezed = false;
+        for row in 0..rows.len() {
+            let Some(Row::Line(line)) = rows.row(row) else {
+                continue;
+            };
+            let bytes = view.get_bytes(line * bpl as u64, bpl);
+            if bytes.is_empty() {
+                continue;
+            }
+
+            let repeated = prev.as_ref().is_some_and(|(l, b)| *l + 1 == line && *b == bytes);
+            if format.squeeze && repeated && bytes.len() == bpl {
+                if !squeezed {
+                    f.write_str("*\n")?;
+                    squeezed = true;
+                }
+            } else {
+                squeezed = false;
+                format.write_line(f, view.get_addr(line * bpl as u64), &bytes, addr_width)?;
+            }
+            prev = Some((line, bytes));
+        }
+
+        let print_end = match format.end_address {
+            EndAddress::Never => false,
+            EndAddress::IfNotEmpty => !view.is_empty(),
+            EndAddress::Always => true,
+        };
+        if print_end {
+            let end = view.regions().last().map_or(0, |r| r.end());
+            format.write_addr(f, view.get_addr(end), addr_width)?;
+            f.write_char('\n')?;
+        }
+        Ok(())
+    }
+}
+
+impl HexView {
+    /// Creates a new `HexView` from a textual hex dump, e.g. pasted from a bug report.
+    ///
+    /// The layout of the dump is detected automatically, supported are the outputs of `hexdump -C`, `xxd`,
+    /// `od -A x -t x1z` and [`dump`](#method.dump) with the default layout. For other layouts use
+    /// [`new_from_dump_format`](#method.new_from_dump_format).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cursive_hexview::HexView;
+    /// let view = HexView::new_from_dump("00000010: 4865 6c6c 6f                             Hello\n").unwrap();
+    /// assert_eq!(view.config().start_addr, 0x10);
+    /// assert_eq!(view.data(), b"Hello");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the layout which matched the most lines, if the dump does not match any layout.
+    pub fn new_from_dump(text: &str) -> Result<Self, ParseError> {
+        let formats = [
+            DumpFormat::default(),
+            DumpFormat::hexdump(),
+            DumpFormat::od(),
+            DumpFormat::xxd(),
+        ];
+        let mut best: Option<ParseError> = None;
+        for format in &formats {
+            match parse_dump(format, text) {
+                Ok(view) => return Ok(view),
+                Err(err)
+                    if best
+                        .as_ref()
+                        .is_none_or(|b| (b.line(), b.column()) < (err.line(), err.column())) =>
+                {
+                    best = Some(err);
+                }
+                Err(_) => {}
+            }
+        }
+        Err(best.expect("there is at least one format"))
+    }
+
+    /// Creates a new `HexView` from a textual hex dump in the given `format`.
+    ///
+    /// The ASCII representation is ignored, lines with a `*` repeat the previous line up to the next
+    /// address. The lowest address becomes the `start_addr` of the view, jumps in the addresses create
+    /// several regions and unknown bytes (`??`) are left unmapped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cursive_hexview::{DumpFormat, HexView};
+    /// let view = HexView::new_from_iter(vec![0; 40]);
+    /// let text = view.dump(&DumpFormat::hexdump()).to_string();
+    ///
+    /// let parsed = HexView::new_from_dump_format(&text, &DumpFormat::hexdump()).unwrap();
+    /// assert_eq!(parsed.data(), view.data());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error with the position of the first malformed line.
+    pub fn new_from_dump_format(text: &str, format: &DumpFormat) -> Result<Self, ParseError> {
+        parse_dump(format, text)
+    }
+
+    /// Returns a textual dump of the data in the given `format`, which implements `Display`.
+    ///
+    /// This can be used to write the data to any `fmt::Write` or `io::Write`, see also
+    /// [`write_dump`](#method.write_dump).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cursive_hexview::{DumpFormat, HexView};
+    /// let view = HexView::new_from_iter(b"Hello");
+    /// let format = DumpFormat::from_config(view.config());
+    /// assert_eq!(view.dump(&format).to_string(), "0: 48 65 6C 6C 6F                                  | Hello\n");
+    /// ```
+    #[must_use]
+    pub const fn dump<'a>(&'a self, format: &'a DumpFormat) -> Dump<'a> {
+        Dump { view: self, format }
+    }
+
+    /// Writes a textual dump of the data in the given `format` to `writer`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cursive_hexview::{DumpFormat, HexView};
+    /// let view = HexView::new_from_iter(b"ABC");
+    /// let mut out = Vec::new();
+    /// view.write_dump(&DumpFormat::xxd(), &mut out).unwrap();
+    /// assert_eq!(out, b"00000000: 4142 43                                  ABC\n");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Fails if writing to `writer` fails.
+    pub fn write_dump<W: io::Write>(&self, format: &DumpFormat, mut writer: W) -> io::Result<()> {
+        write!(writer, "{}", self.dump(format))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dump_presets() {
+        let view = HexView::new_from_iter(b"Hello, World!\n");
+        assert_eq!(
+            view.dump(&DumpFormat::hexdump()).to_string(),
+            "00000000  48 65 6c 6c 6f 2c 20 57  6f 72 6c 64 21 0a        |Hello, World!.|\n0000000e\n"
+        );
+        assert_eq!(
+            view.dump(&DumpFormat::xxd()).to_string(),
+            "00000000: 4865 6c6c 6f2c 2057 6f72 6c64 210a       Hello, World!.\n"
+        );
+        assert_eq!(
+            view.dump(&DumpFormat::od()).to_string(),
+            "000000 48 65 6c 6c 6f 2c 20 57 6f 72 6c 64 21 0a        >Hello, World!.<\n00000e\n"
+        );
+    }
+
+    #[test]
+    fn dump_squeezes_repeated_lines() {
+        let view = HexView::new_from_iter(vec![0; 40]);
+        assert_eq!(
+            view.dump(&DumpFormat::od()).to_string(),
+            "000000 00 00 00 00 00 00 00 00 00 00 00 00 00 00 00 00  >................<\n\
+             *\n\
+             000020 00 00 00 00 00 00 00 00                          >........<\n\
+             000028\n"
+        );
+    }
+
+    fn error(text: &str) -> (usize, usize, ParseErrorKind) {
+        let err = HexView::new_from_dump_format(text, &DumpFormat::od()).unwrap_err();
+        (err.line(), err.column(), err.kind().clone())
+    }
+
+    #[test]
+    fn parse_reports_error_of_best_layout() {
+        let err = HexView::new_from_dump("00000010: 4865 6x6c 6f                             Hello\n").unwrap_err();
+        assert_eq!((err.line(), err.column()), (1, 17));
+        assert_eq!(err.kind(), &ParseErrorKind::InvalidHexDigit('x'));
+    }
+
+    #[test]
+    fn parse_expands_squeezed_lines() {
+        let text = "000000 01 01 01 01 01 01 01 01 01 01 01 01 01 01 01 01  >................<\n*\n000030 02  >.<\n";
+        let view = HexView::new_from_dump_format(text, &DumpFormat::od()).unwrap();
+        let mut expected = vec![1; 0x30];
+        expected.push(2);
+        assert_eq!(view.data(), expected.as_slice());
+    }
+
+    #[test]
+    fn parse_placeholders_and_gaps() {
+        let text = "000000 01 ?? 03  >. .<\n000100 04  >.<\n";
+        let view = HexView::new_from_dump_format(text, &DumpFormat::od()).unwrap();
+        // `??` is left out like the lines which are skipped
+        assert_eq!(view.regions().len(), 3);
+        assert_eq!(view.byte(0), Some(1));
+        assert_eq!(view.byte(1), None);
+        assert_eq!(view.byte(2), Some(3));
+        assert_eq!(view.byte(0x100), Some(4));
+    }
+
+    #[test]
+    fn parse_rejects_malformed_lines() {
+        assert_eq!(error("*\n"), (1, 1, ParseErrorKind::UnexpectedCharacter('*')));
+        assert_eq!(error("x00000 01\n"), (1, 1, ParseErrorKind::InvalidHexDigit('x')));
+        assert_eq!(error("000000 0\n"), (1, 9, ParseErrorKind::UnexpectedEndOfLine));
+        assert_eq!(
+            error("000000 01 02  |..|\n"),
+            (1, 15, ParseErrorKind::UnexpectedCharacter('|'))
+        );
+        // only the last line before a gap may be short
+        assert_eq!(
+            error("000000 01 02  >..<\n000002 03  >.<\n"),
+            (1, 1, ParseErrorKind::LengthMismatch { expected: 16, found: 2 })
+        );
+    }
+
+    #[test]
+    fn parse_dump_of_each_preset() {
+        let view = HexView::new_from_iter((0..=255).cycle().take(600).collect::<Vec<u8>>());
+        for format in [
+            DumpFormat::default(),
+            DumpFormat::hexdump(),
+            DumpFormat::od(),
+            DumpFormat::xxd(),
+        ] {
+            let parsed = HexView::new_from_dump(&view.dump(&format).to_string()).unwrap();
+            assert_eq!(parsed.data(), view.data());
+        }
+    }
+}
@@ -0,0 +1,596 @@
+use std::borrow::Borrow;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use cursive::direction::Direction;
+use cursive::event::{Event, EventResult, Key};
+use cursive::menu;
+use cursive::view::{CannotFocus, View};
+use cursive::views::{EditView, MenuPopup};
+use cursive::{Printer, Rect, Vec2, With};
+use unicode_width::UnicodeWidthStr;
+
+use parse::decode_hex;
+use {Action, HexStatusView, HexView};
+
+/// An inline prompt of the editor, which asks for one line of text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Prompt {
+    Search,
+    Goto,
+    Open,
+    SaveAs,
+}
+
+impl Prompt {
+    const fn label(self) -> &'static str {
+        match self {
+            Self::Search => "Search (hex bytes or \"text\"): ",
+            Self::Goto => "Go to address (0x.. or decimal): ",
+            Self::Open => "Open file: ",
+            Self::SaveAs => "Save as: ",
+        }
+    }
+}
+
+/// A command triggered by the menu or the keymap of the hex view, which is run on the next event or layout
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Command {
+    Prompt(Prompt),
+    Save,
+    Perform(Action),
+    Copy,
+}
+
+/// The commands queued by callbacks, which only get access to the `Cursive` root
+type CommandQueue = Arc<Mutex<Vec<Command>>>;
+
+/// A callback which receives the copied bytes
+type CopyCallback = Arc<dyn Fn(&[u8]) + Send + Sync>;
+
+/// A complete hex editor, made of a [`HexView`], a [`HexStatusView`] and a line for prompts and messages.
+///
+/// Besides the keys of the hex view, <kbd>F10</kbd> opens a menu with the common actions: opening and saving
+/// files, undo and redo, searching, jumping to an address and back again and copying the selection. Searching and
+/// jumping ask for their input in the bottom line of the editor, <kbd>Enter</kbd> confirms and <kbd>Esc</kbd>
+/// cancels the prompt. The same actions can be added to the menubar of the application with [`menu`](#method.menu).
+///
+/// The editor handles the [`on_command`] actions of its hex view with its prompts. A callback which the hex view
+/// had before is still run afterwards.
+///
+/// [`HexView`]: struct.HexView.html
+/// [`HexStatusView`]: struct.HexStatusView.html
+/// [`on_command`]: struct.HexView.html#method.set_on_command
+///
+/// # Examples
+///
+/// ```
+/// extern crate cursive;
+/// extern crate cursive_hexview;
+///
+/// use cursive::view::Resizable;
+/// use cursive_hexview::{DisplayState, HexEditorView, HexView};
+///
+/// fn main() {
+///     let view = HexView::new_from_iter(b"Hello, World!").display_state(DisplayState::Editable);
+///     let editor = HexEditorView::new(view);
+///
+///     let mut cur = cursive::dummy();
+///     cur.add_fullscreen_layer(editor.full_screen());
+///
+///     // cur.run();
+/// }
+/// ```
+pub struct HexEditorView {
+    hex: HexView,
+    status: HexStatusView,
+    show_status: bool,
+    file_commands: bool,
+    path: Option<PathBuf>,
+    /// the active prompt and the input line it uses
+    prompt: Option<Prompt>,
+    input: EditView,
+    /// the last message shown in the bottom line
+    message: String,
+    /// the bytes copied last
+    clipboard: Vec<u8>,
+    on_copy: Option<CopyCallback>,
+    commands: CommandQueue,
+    /// whether the prompt, the message or the footer changed since the last layout
+    layout_changed: bool,
+}
+
+impl fmt::Debug for HexEditorView {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HexEditorView")
+            .field("hex", &self.hex)
+            .field("path", &self.path)
+            .field("prompt", &self.prompt)
+            .field("message", &self.message)
+            .finish_non_exhaustive()
+    }
+}
+
+impl HexEditorView {
+    /// Creates an editor around `hex`.
+    #[must_use]
+    pub fn new(mut hex: HexView) -> Self {
+        let commands = CommandQueue::default();
+        let queue = Arc::clone(&commands);
+        let previous = hex.on_command.take();
+        hex.set_on_command(move |s, action| {
+            let prompt = if action == Action::Goto {
+                Prompt::Goto
+            } else {
+                Prompt::Search
+            };
+            push_command(&queue, Command::Prompt(prompt));
+            if let Some(previous) = &previous {
+                previous(s, action);
+            }
+        });
+        Self {
+            status: hex.status_view(),
+            hex,
+            show_status: true,
+            file_commands: true,
+            path: None,
+            prompt: None,
+            input: EditView::new(),
+            message: String::new(),
+            clipboard: Vec::new(),
+            on_copy: None,
+            commands,
+            layout_changed: true,
+        }
+    }
+
+    /// Returns the hex view of this editor.
+    #[must_use]
+    pub const fn hex_view(&self) -> &HexView {
+        &self.hex
+    }
+
+    /// Returns a mutable reference to the hex view of this editor, e.g. to change its config or keymap.
+    pub fn hex_view_mut(&mut self) -> &mut HexView {
+        &mut self.hex
+    }
+
+    /// Controls whether the status line is displayed, which is the default.
+    pub fn set_show_status(&mut self, show: bool) {
+        self.show_status = show;
+        self.layout_changed = true;
+    }
+
+    /// [`set_show_status`](#method.set_show_status)
+    #[must_use]
+    pub fn show_status(self, show: bool) -> Self {
+        self.with(|s| s.set_show_status(show))
+    }
+
+    /// Controls whether the menu offers to open and save files, which is the default.
+    ///
+    /// Applications which manage the data on their own should disable them.
+    pub fn set_file_commands(&mut self, enabled: bool) {
+        self.file_commands = enabled;
+    }
+
+    /// [`set_file_commands`](#method.set_file_commands)
+    #[must_use]
+    pub fn file_commands(self, enabled: bool) -> Self {
+        self.with(|s| s.set_file_commands(enabled))
+    }
+
+    /// Appends `data` to the hex view, see [`HexView::append`](struct.HexView.html#method.append).
+    ///
+    /// This allows a [`DataSender`](struct.DataSender.html) to stream into the editor.
+    pub fn append<B: Borrow<u8>, I: IntoIterator<Item = B>>(&mut self, data: I) {
+        self.hex.append(data);
+    }
+
+    /// Returns the file which is edited, if any.
+    #[must_use]
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Sets the file which [`save`](#method.save) writes to, without loading it.
+    pub fn set_path<P: Into<PathBuf>>(&mut self, path: P) {
+        self.path = Some(path.into());
+    }
+
+    /// Loads the file at `path` into the hex view and remembers it for [`save`](#method.save).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read, the editor is not altered in this case.
+    pub fn open<P: Into<PathBuf>>(&mut self, path: P) -> io::Result<()> {
+        let path = path.into();
+        let data = fs::read(&path)?;
+        self.hex.set_data(data);
+        self.path = Some(path);
+        Ok(())
+    }
+
+    /// Writes the data of the hex view to the file it was opened from and marks it as saved.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no file or it cannot be written.
+    pub fn save(&mut self) -> io::Result<()> {
+        let path = self
+            .path
+            .clone()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no file to save to"))?;
+        self.save_as(path)
+    }
+
+    /// Writes the data of the hex view to `path`, which is used by [`save`](#method.save) from now on.
+    ///
+    /// A plain file can only hold a single region without unknown bytes, other views are not saved, see
+    /// [`write_ihex`](struct.HexView.html#method.write_ihex) for sparse data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cursive_hexview::{HexEditorView, HexView};
+    /// let path = std::env::temp_dir().join(format!("hexview-save-{}", std::process::id()));
+    /// let mut editor = HexEditorView::new(HexView::new_from_iter(b"ABC"));
+    /// editor.save_as(&path).unwrap();
+    /// assert_eq!(std::fs::read(&path).unwrap(), b"ABC");
+    /// # std::fs::remove_file(&path).unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the view has several regions or unknown bytes, or if the file cannot be written.
+    pub fn save_as<P: Into<PathBuf>>(&mut self, path: P) -> io::Result<()> {
+        let path = path.into();
+        let regions = self.hex.regions();
+        if regions.len() > 1 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "the data has several regions, which a plain file cannot hold",
+            ));
+        }
+        if regions.iter().any(|r| (0..r.len()).any(|i| !r.is_known(i))) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "the data has unknown bytes, which a plain file cannot hold",
+            ));
+        }
+        fs::write(&path, self.hex.data())?;
+        self.path = Some(path);
+        self.hex.mark_saved();
+        Ok(())
+    }
+
+    /// Returns the bytes copied last, either the selection or the byte under the cursor.
+    ///
+    /// This is an internal copy, use [`set_on_copy`](#method.set_on_copy) to pass the bytes on to the clipboard of
+    /// the system.
+    #[must_use]
+    pub fn clipboard(&self) -> &[u8] {
+        &self.clipboard
+    }
+
+    /// Sets a callback which receives the bytes whenever the user copies, e.g. to put them into the clipboard of
+    /// the system.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::sync::{Arc, Mutex};
+    /// # use cursive_hexview::{HexEditorView, HexView};
+    /// let copied = Arc::new(Mutex::new(String::new()));
+    /// let sink = Arc::clone(&copied);
+    /// let editor = HexEditorView::new(HexView::new_from_iter(b"ABC"))
+    ///     .on_copy(move |bytes| *sink.lock().unwrap() = String::from_utf8_lossy(bytes).into_owned());
+    /// ```
+    pub fn set_on_copy<F: Fn(&[u8]) + Send + Sync + 'static>(&mut self, callback: F) {
+        self.on_copy = Some(Arc::new(callback));
+    }
+
+    /// [`set_on_copy`](#method.set_on_copy)
+    #[must_use]
+    pub fn on_copy<F: Fn(&[u8]) + Send + Sync + 'static>(self, callback: F) -> Self {
+        self.with(|s| s.set_on_copy(callback))
+    }
+
+    /// Returns the message which is displayed in the bottom line, e.g. the result of the last command.
+    #[must_use]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Creates a menu with the actions of this editor, e.g. for the menubar of the application.
+    ///
+    /// The menu only affects this editor, so every editor needs its own menu.
+    #[must_use]
+    pub fn menu(&self) -> menu::Tree {
+        let mut tree = menu::Tree::new();
+        let mut add = |label: &str, command: Command| {
+            let queue = Arc::clone(&self.commands);
+            tree.add_leaf(label, move |_| push_command(&queue, command));
+        };
+        if self.file_commands {
+            add("Open...", Command::Prompt(Prompt::Open));
+            add("Save", Command::Save);
+            add("Save as...", Command::Prompt(Prompt::SaveAs));
+        }
+        add("Undo", Command::Perform(Action::Undo));
+        add("Redo", Command::Perform(Action::Redo));
+        add("Find...", Command::Prompt(Prompt::Search));
+        add("Find next", Command::Perform(Action::FindNext));
+        add("Go to...", Command::Prompt(Prompt::Goto));
+        add("Back", Command::Perform(Action::JumpBack));
+        add("Forward", Command::Perform(Action::JumpForward));
+        add("Follow pointer", Command::Perform(Action::FollowPointer));
+        add("Copy", Command::Copy);
+        tree.add_leaf("Keys", self.hex.show_help());
+        tree
+    }
+
+    /// Runs all queued commands
+    fn run_commands(&mut self) {
+        let commands = std::mem::take(&mut *self.commands.lock().unwrap_or_else(PoisonError::into_inner));
+        for command in commands {
+            self.run_command(command);
+        }
+    }
+
+    fn run_command(&mut self, command: Command) {
+        self.layout_changed = true;
+        match command {
+            Command::Prompt(Prompt::SaveAs | Prompt::Open) if !self.file_commands => {}
+            Command::Prompt(prompt) => {
+                self.prompt = Some(prompt);
+                self.input.set_content("");
+            }
+            Command::Save if self.path.is_none() => self.run_command(Command::Prompt(Prompt::SaveAs)),
+            Command::Save => {
+                self.message = match self.save() {
+                    Ok(()) => format!("Saved {} bytes", self.hex.len()),
+                    Err(e) => format!("Error: {e}"),
+                };
+            }
+            Command::Perform(action) => {
+                self.hex.perform(action);
+            }
+            Command::Copy => {
+                self.clipboard = self.hex.selected_bytes();
+                if let Some(on_copy) = &self.on_copy {
+                    on_copy(&self.clipboard);
+                }
+                self.message = format!("Copied {} bytes", self.clipboard.len());
+            }
+        }
+    }
+
+    /// Runs the active prompt with the text typed into it
+    fn submit(&mut self, prompt: Prompt) {
+        let input = self.input.get_content();
+        let input = input.trim();
+        self.message = match prompt {
+            Prompt::Search => match parse_pattern(input) {
+                Some(pattern) if self.hex.search(&pattern) => String::new(),
+                Some(_) => "Not found".to_string(),
+                None => format!("Invalid pattern {input:?}"),
+            },
+            Prompt::Goto => match parse_addr(input) {
+                Some(addr) => {
+                    self.hex.jump_to(addr);
+                    String::new()
+                }
+                None => format!("Invalid address {input:?}"),
+            },
+            Prompt::Open => match self.open(input) {
+                Ok(()) => format!("Opened {input}"),
+                Err(e) => format!("Error: {e}"),
+            },
+            Prompt::SaveAs => match self.save_as(input) {
+                Ok(()) => format!("Saved {} bytes to {input}", self.hex.len()),
+                Err(e) => format!("Error: {e}"),
+            },
+        };
+    }
+
+    /// Returns the number of rows below the hex view
+    fn get_footer_height(&self) -> usize {
+        if self.show_status {
+            2
+        } else {
+            1
+        }
+    }
+}
+
+/// Adds `command` to `queue`
+fn push_command(queue: &CommandQueue, command: Command) {
+    queue.lock().unwrap_or_else(PoisonError::into_inner).push(command);
+}
+
+/// Parses a search pattern, which is either hex digits, optionally separated by spaces, or text in quotes
+fn parse_pattern(input: &str) -> Option<Vec<u8>> {
+    let pattern = match input.strip_prefix('"') {
+        Some(text) => text.strip_suffix('"').unwrap_or(text).as_bytes().to_vec(),
+        None => decode_hex(&input.split_whitespace().collect::<String>()).ok()?,
+    };
+    (!pattern.is_empty()).then_some(pattern)
+}
+
+/// Parses an address, which is either hex with a `0x` prefix or decimal
+fn parse_addr(input: &str) -> Option<u64> {
+    match input.strip_prefix("0x").or_else(|| input.strip_prefix("0X")) {
+        Some(hex) => u64::from_str_radix(&hex.replace('_', ""), 16).ok(),
+        None => input.replace('_', "").parse().ok(),
+    }
+}
+
+impl View for HexEditorView {
+    fn draw(&self, printer: &Printer) {
+        let footer = self.get_footer_height();
+        let hex_height = printer.size.y.saturating_sub(footer);
+        self.hex.draw(&printer.cropped((printer.size.x, hex_height)));
+
+        let mut y = hex_height;
+        if self.show_status {
+            self.status.draw(&printer.offset((0, y)).cropped((printer.size.x, 1)));
+            y += 1;
+        }
+
+        let line = printer.offset((0, y)).cropped((printer.size.x, 1));
+        match self.prompt {
+            Some(prompt) => {
+                let label = prompt.label();
+                line.print((0, 0), label);
+                self.input.draw(&line.offset((label.width(), 0)).focused(true));
+            }
+            None if self.message.is_empty() => line.print((0, 0), "F10: menu"),
+            None => line.print((0, 0), &self.message),
+        }
+    }
+
+    fn layout(&mut self, size: Vec2) {
+        self.run_commands();
+        let footer = self.get_footer_height();
+        self.hex.layout(size.map_y(|y| y.saturating_sub(footer)));
+        self.status.layout((size.x, 1).into());
+        let label = self.prompt.map_or(0, |p| p.label().width());
+        self.input.layout((size.x.saturating_sub(label), 1).into());
+        self.layout_changed = false;
+    }
+
+    fn needs_relayout(&self) -> bool {
+        // queued commands run in the next layout
+        self.layout_changed
+            || self.hex.needs_relayout()
+            || !self.commands.lock().unwrap_or_else(PoisonError::into_inner).is_empty()
+    }
+
+    fn required_size(&mut self, constraint: Vec2) -> Vec2 {
+        let footer = self.get_footer_height();
+        let hex = self.hex.required_size(constraint.map_y(|y| y.saturating_sub(footer)));
+        let status = self.status.required_size(constraint);
+        Vec2::new(std::cmp::max(hex.x, status.x), hex.y + footer)
+    }
+
+    fn on_event(&mut self, event: Event) -> EventResult {
+        self.run_commands();
+        if event == Event::Refresh {
+            return self.hex.on_event(event);
+        }
+
+        if let Some(prompt) = self.prompt {
+            self.layout_changed = true;
+            return match event {
+                Event::Key(Key::Enter) => {
+                    self.prompt = None;
+                    self.submit(prompt);
+                    EventResult::Consumed(None)
+                }
+                Event::Key(Key::Esc) => {
+                    self.prompt = None;
+                    EventResult::Consumed(None)
+                }
+                Event::Mouse { .. } => EventResult::Ignored,
+                event => {
+                    self.input.on_event(event);
+                    EventResult::Consumed(None)
+                }
+            };
+        }
+
+        match event {
+            Event::Key(Key::F10) => {
+                let menu = Arc::new(self.menu());
+                EventResult::with_cb(move |s| s.add_layer(MenuPopup::new(Arc::clone(&menu))))
+            }
+            Event::Mouse { position, offset, .. }
+                if position.y.saturating_sub(offset.y) >= self.hex.size.map_or(0, |size| size.y) =>
+            {
+                EventResult::Ignored
+            }
+            event => {
+                if !self.message.is_empty() {
+                    self.message.clear();
+                    self.layout_changed = true;
+                }
+                self.hex.on_event(event)
+            }
+        }
+    }
+
+    fn important_area(&self, view_size: Vec2) -> Rect {
+        let footer = self.get_footer_height();
+        self.hex.important_area(view_size.map_y(|y| y.saturating_sub(footer)))
+    }
+
+    fn take_focus(&mut self, source: Direction) -> Result<EventResult, CannotFocus> {
+        self.hex.take_focus(source)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    use cursive::Cursive;
+
+    use super::*;
+    use {DisplayState, Region};
+
+    #[test]
+    fn commands_keep_previous_callback() {
+        let called = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&called);
+        let hex = HexView::new_from_iter(b"ABC")
+            .display_state(DisplayState::Enabled)
+            .on_command(move |_, _| flag.store(true, Ordering::SeqCst));
+        let mut editor = HexEditorView::new(hex);
+        editor.layout((80, 10).into());
+        assert!(!editor.needs_relayout());
+
+        let mut cur = Cursive::new();
+        editor.on_event(Event::CtrlChar('g')).process(&mut cur);
+        assert!(called.load(Ordering::SeqCst));
+
+        // the queued command asks for the layout which runs it
+        assert!(editor.needs_relayout());
+        editor.layout((80, 10).into());
+        assert_eq!(editor.prompt, Some(Prompt::Goto));
+        assert!(!editor.needs_relayout());
+    }
+
+    #[test]
+    fn copy_calls_callback() {
+        let copied = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&copied);
+        let mut hex = HexView::new_from_iter(b"ABCD");
+        hex.set_selection(1..=2);
+        let mut editor = HexEditorView::new(hex).on_copy(move |bytes| *sink.lock().unwrap() = bytes.to_vec());
+
+        editor.run_command(Command::Copy);
+        assert_eq!(editor.clipboard(), b"BC");
+        assert_eq!(*copied.lock().unwrap(), b"BC");
+    }
+
+    #[test]
+    fn save_refuses_data_a_plain_file_cannot_hold() {
+        let path = std::env::temp_dir().join(format!("hexview-refuse-{}", std::process::id()));
+
+        let regions = vec![Region::new("a", 0, b"ABC"), Region::new("b", 0x100, b"DEF")];
+        let mut editor = HexEditorView::new(HexView::new_from_regions(regions).unwrap());
+        assert_eq!(editor.save_as(&path).unwrap_err().kind(), io::ErrorKind::InvalidData);
+
+        let regions = vec![Region::new_partial("a", 0, vec![Some(1), None])];
+        let mut editor = HexEditorView::new(HexView::new_from_regions(regions).unwrap());
+        assert_eq!(editor.save_as(&path).unwrap_err().kind(), io::ErrorKind::InvalidData);
+
+        assert!(!path.exists());
+        assert_eq!(editor.path(), None);
+    }
+}
@@ -0,0 +1,159 @@
+use region::Region;
+
+/// A single reversible change of the data
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Edit {
+    /// The byte at `offset` changed from `old` to `new`
+    Set { offset: u64, old: u8, new: u8 },
+    /// `byte` was inserted at `offset`, moving the following bytes of its region back
+    Insert { offset: u64, byte: u8 },
+    /// `byte` was removed from `offset`, moving the following bytes of its region forth
+    Remove { offset: u64, byte: u8 },
+}
+
+impl Edit {
+    /// Returns the offset of the changed byte
+    pub(crate) const fn offset(self) -> u64 {
+        match self {
+            Self::Set { offset, .. } | Self::Insert { offset, .. } | Self::Remove { offset, .. } => offset,
+        }
+    }
+
+    /// Checks whether the edit changes the length of the data
+    pub(crate) const fn is_resize(self) -> bool {
+        !matches!(self, Self::Set { .. })
+    }
+
+    /// Returns the edit which reverts this one
+    pub(crate) const fn inverse(self) -> Self {
+        match self {
+            Self::Set { offset, old, new } => Self::Set {
+                offset,
+                old: new,
+                new: old,
+            },
+            Self::Insert { offset, byte } => Self::Remove { offset, byte },
+            Self::Remove { offset, byte } => Self::Insert { offset, byte },
+        }
+    }
+
+    /// Checks whether the edit can be applied to `regions`
+    ///
+    /// This is not the case if the byte is not mapped or unknown or, for insertions, if the region would grow
+    /// into the next one.
+    pub(crate) fn can_apply(self, regions: &[Region]) -> bool {
+        let offset = self.offset();
+        let Some(idx) = regions.partition_point(|r| r.offset() <= offset).checked_sub(1) else {
+            return false;
+        };
+        let next = regions.get(idx + 1).map_or(u64::MAX, Region::offset);
+        let region = &regions[idx];
+        let pos = (offset - region.offset()) as usize;
+        match self {
+            Self::Set { .. } | Self::Remove { .. } => region.contains(offset) && region.is_known(pos),
+            Self::Insert { .. } => offset <= region.end() && region.end() < next,
+        }
+    }
+
+    /// Applies the edit to `regions`
+    ///
+    /// Returns `false` without changing anything if the edit [cannot be applied](#method.can_apply).
+    pub(crate) fn apply(self, regions: &mut [Region]) -> bool {
+        if !self.can_apply(regions) {
+            return false;
+        }
+        let offset = self.offset();
+        let idx = regions.partition_point(|r| r.offset() <= offset) - 1;
+        let region = &mut regions[idx];
+        let pos = (offset - region.offset()) as usize;
+        match self {
+            Self::Set { new, .. } => region.set(pos, new),
+            Self::Insert { byte, .. } => region.insert(pos, byte),
+            Self::Remove { .. } => {
+                region.remove(pos);
+            }
+        }
+        true
+    }
+}
+
+/// The edits which can be undone and redone
+#[derive(Debug, Clone)]
+pub(crate) struct History {
+    undo: Vec<Edit>,
+    redo: Vec<Edit>,
+    /// the number of undoable edits when the data was saved, `None` if that state is unreachable
+    saved: Option<usize>,
+}
+
+impl Default for History {
+    fn default() -> Self {
+        Self {
+            undo: Vec::new(),
+            redo: Vec::new(),
+            saved: Some(0),
+        }
+    }
+}
+
+impl History {
+    /// Records an edit which has been applied, which makes the reverted ones unreachable
+    pub(crate) fn push(&mut self, edit: Edit) {
+        if self.saved.is_some_and(|saved| saved > self.undo.len()) {
+            self.saved = None;
+        }
+        self.undo.push(edit);
+        self.redo.clear();
+    }
+
+    /// Returns the edit which reverts the last applied one
+    pub(crate) fn next_undo(&self) -> Option<Edit> {
+        self.undo.last().map(|edit| edit.inverse())
+    }
+
+    /// Returns the last reverted edit
+    pub(crate) fn next_redo(&self) -> Option<Edit> {
+        self.redo.last().copied()
+    }
+
+    /// Records that the [`next_undo`](#method.next_undo) edit has been applied
+    pub(crate) fn undo(&mut self) {
+        if let Some(edit) = self.undo.pop() {
+            self.redo.push(edit);
+        }
+    }
+
+    /// Records that the [`next_redo`](#method.next_redo) edit has been applied
+    pub(crate) fn redo(&mut self) {
+        if let Some(edit) = self.redo.pop() {
+            self.undo.push(edit);
+        }
+    }
+
+    /// Checks whether there is an edit to undo
+    pub(crate) fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    /// Checks whether there is an edit to redo
+    pub(crate) fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Checks whether the data differs from the last saved state
+    pub(crate) fn is_dirty(&self) -> bool {
+        self.saved != Some(self.undo.len())
+    }
+
+    /// Remembers the current state as saved
+    pub(crate) fn mark_saved(&mut self) {
+        self.saved = Some(self.undo.len());
+    }
+
+    /// Forgets all edits after the data has been replaced, `dirty` tells whether the new data is unsaved
+    pub(crate) fn clear(&mut self, dirty: bool) {
+        self.undo.clear();
+        self.redo.clear();
+        self.saved = (!dirty).then_some(0);
+    }
+}
@@ -0,0 +1,254 @@
+use std::io::{self, Write};
+
+use parse::{build_view, Chunk, ParseError, ParseErrorKind, Record};
+use HexView;
+
+const DATA: u8 = 0x00;
+const END_OF_FILE: u8 = 0x01;
+const EXTENDED_SEGMENT_ADDRESS: u8 = 0x02;
+const START_SEGMENT_ADDRESS: u8 = 0x03;
+const EXTENDED_LINEAR_ADDRESS: u8 = 0x04;
+const START_LINEAR_ADDRESS: u8 = 0x05;
+
+/// Number of data bytes written per record
+const BYTES_PER_RECORD: usize = 16;
+
+/// Writes a single record, including start code and checksum
+fn write_record<W: Write>(writer: &mut W, addr: u16, kind: u8, data: &[u8]) -> io::Result<()> {
+    let [addr_high, addr_low] = addr.to_be_bytes();
+    let mut record = vec![data.len() as u8, addr_high, addr_low, kind];
+    record.extend_from_slice(data);
+    let checksum = record.iter().fold(0u8, |acc, b| acc.wrapping_add(*b)).wrapping_neg();
+
+    write!(writer, ":")?;
+    for b in record.iter().chain(Some(&checksum)) {
+        write!(writer, "{b:02X}")?;
+    }
+    writeln!(writer)
+}
+
+impl HexView {
+    /// Creates a new `HexView` from the content of an Intel HEX file.
+    ///
+    /// The lowest address found in the file becomes the `start_addr` of the view. Data which is not
+    /// contiguous is split into several regions, see [`set_regions`](#method.set_regions).
+    /// Start address records (types `03` and `05`) are ignored and everything after the end of file
+    /// record is not parsed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cursive_hexview::HexView;
+    /// let view = HexView::new_from_ihex(":020000040800F2\n:0400100001020304E2\n:00000001FF\n").unwrap();
+    /// assert_eq!(view.config().start_addr, 0x0800_0010);
+    /// assert_eq!(view.data(), &[1, 2, 3, 4]);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error with the position of the first malformed record, a record with a wrong checksum or
+    /// data which is defined twice.
+    pub fn new_from_ihex(text: &str) -> Result<Self, ParseError> {
+        let mut chunks = Vec::new();
+        let mut base = 0u64;
+
+        for (idx, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let record = Record::new(idx + 1, line, ':')?;
+            let bytes = record.bytes()?;
+            if bytes.len() < 5 {
+                return Err(record.error_at(line.len(), ParseErrorKind::UnexpectedEndOfLine));
+            }
+            let len = bytes[0] as usize;
+            if bytes.len() != len + 5 {
+                return Err(record.error_at_byte(
+                    0,
+                    ParseErrorKind::LengthMismatch {
+                        expected: len,
+                        found: bytes.len().saturating_sub(5),
+                    },
+                ));
+            }
+            let checksum = bytes[len + 4];
+            let expected = bytes[..len + 4]
+                .iter()
+                .fold(0u8, |acc, b| acc.wrapping_add(*b))
+                .wrapping_neg();
+            if checksum != expected {
+                return Err(record.error_at_byte(
+                    len + 4,
+                    ParseErrorKind::ChecksumMismatch {
+                        expected,
+                        found: checksum,
+                    },
+                ));
+            }
+
+            let offset = u64::from(u16::from_be_bytes([bytes[1], bytes[2]]));
+            let data = &bytes[4..len + 4];
+            let expect_len = |expected: usize| {
+                if len == expected {
+                    Ok(())
+                } else {
+                    Err(record.error_at_byte(0, ParseErrorKind::LengthMismatch { expected, found: len }))
+                }
+            };
+            match bytes[3] {
+                DATA => chunks.push(Chunk {
+                    addr: base + offset,
+                    line: idx + 1,
+                    data: data.to_vec(),
+                }),
+                END_OF_FILE => break,
+                EXTENDED_SEGMENT_ADDRESS => {
+                    expect_len(2)?;
+                    base = u64::from(u16::from_be_bytes([data[0], data[1]])) << 4;
+                }
+                EXTENDED_LINEAR_ADDRESS => {
+                    expect_len(2)?;
+                    base = u64::from(u16::from_be_bytes([data[0], data[1]])) << 16;
+                }
+                START_SEGMENT_ADDRESS | START_LINEAR_ADDRESS => expect_len(4)?,
+                kind => {
+                    return Err(record.error_at_byte(3, ParseErrorKind::UnsupportedRecord(format!("{kind:02X}"))));
+                }
+            }
+        }
+
+        build_view(chunks)
+    }
+
+    /// Writes the current data of this view as Intel HEX.
+    ///
+    /// Every region is written with its absolute address, extended linear address records are inserted
+    /// where necessary. Unknown bytes are left out, like unmapped ones. The output is terminated with an end of
+    /// file record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cursive_hexview::HexView;
+    /// let view = HexView::new_from_iter(&[1, 2, 3, 4]);
+    /// let mut out = Vec::new();
+    /// view.write_ihex(&mut out).unwrap();
+    /// assert_eq!(out, b":0400000001020304F2\n:00000001FF\n");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`io::ErrorKind::InvalidInput`] if any address exceeds 32 bits, or if writing fails.
+    pub fn write_ihex<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let mut upper = 0u16;
+        for (offset, mut data) in self
+            .regions()
+            .iter()
+            .flat_map(|r| r.known_runs().map(move |(pos, data)| (r.offset() + pos as u64, data)))
+        {
+            let mut addr = self.config().start_addr.checked_add(offset);
+            while !data.is_empty() {
+                let current = addr.filter(|a| *a <= u64::from(u32::MAX)).ok_or_else(|| {
+                    io::Error::new(io::ErrorKind::InvalidInput, "Intel HEX only supports 32 bit addresses")
+                })? as u32;
+                let [high, low] = [(current >> 16) as u16, current as u16];
+                if high != upper {
+                    write_record(&mut writer, 0, EXTENDED_LINEAR_ADDRESS, &high.to_be_bytes())?;
+                    upper = high;
+                }
+
+                // a record must not cross a 64 KiB boundary
+                let len = data.len().min(BYTES_PER_RECORD).min(0x1_0000 - usize::from(low));
+                let (record, rest) = data.split_at(len);
+                write_record(&mut writer, low, DATA, record)?;
+                data = rest;
+                addr = current.checked_add(len as u32).map(u64::from);
+            }
+        }
+        write_record(&mut writer, 0, END_OF_FILE, &[])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Region;
+
+    fn error(text: &str) -> (usize, usize, ParseErrorKind) {
+        let err = HexView::new_from_ihex(text).unwrap_err();
+        (err.line(), err.column(), err.kind().clone())
+    }
+
+    #[test]
+    fn reports_malformed_records() {
+        assert_eq!(error("0400100001020304E2\n"), (1, 1, ParseErrorKind::InvalidStartCode));
+        assert_eq!(
+            error(":0400100001020304E3\n"),
+            (
+                1,
+                18,
+                ParseErrorKind::ChecksumMismatch {
+                    expected: 0xE2,
+                    found: 0xE3
+                }
+            )
+        );
+        assert_eq!(
+            error("\n:0400100001020304\n"),
+            (2, 2, ParseErrorKind::LengthMismatch { expected: 4, found: 3 })
+        );
+        assert_eq!(error(":0000\n"), (1, 6, ParseErrorKind::UnexpectedEndOfLine));
+        assert_eq!(
+            error(":00000006FA\n"),
+            (1, 8, ParseErrorKind::UnsupportedRecord("06".into()))
+        );
+        assert_eq!(
+            error(":0100000400FB\n"),
+            (1, 2, ParseErrorKind::LengthMismatch { expected: 2, found: 1 })
+        );
+        assert_eq!(
+            error(":0100000042BD\n:0100000042BD\n"),
+            (2, 1, ParseErrorKind::OverlappingData(0))
+        );
+    }
+
+    #[test]
+    fn applies_address_records() {
+        let view = HexView::new_from_ihex(":020000021000EC\n:0100000042BD\n:00000001FF\nnot parsed\n").unwrap();
+        assert_eq!(view.config().start_addr, 0x1_0000);
+        assert_eq!(view.data(), &[0x42]);
+    }
+
+    #[test]
+    fn splits_records_at_64k_boundaries() {
+        let mut view = HexView::new_from_iter(0..16);
+        view.config_mut().start_addr = 0xFFF8;
+        let mut out = Vec::new();
+        view.write_ihex(&mut out).unwrap();
+
+        let text = String::from_utf8(out).unwrap();
+        assert_eq!(text.lines().count(), 4);
+        let parsed = HexView::new_from_ihex(&text).unwrap();
+        assert_eq!(parsed.config().start_addr, 0xFFF8);
+        assert_eq!(parsed.data(), view.data());
+    }
+
+    #[test]
+    fn rejects_addresses_above_32_bits() {
+        let mut view = HexView::new_from_iter([0; 4]);
+        view.config_mut().start_addr = 0xFFFF_FFFE;
+        let err = view.write_ihex(Vec::new()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn leaves_out_unknown_bytes() {
+        let regions = vec![Region::new_partial("flash", 0, vec![Some(1), None, Some(3), Some(4)])];
+        let view = HexView::new_from_regions(regions).unwrap();
+        let mut out = Vec::new();
+        view.write_ihex(&mut out).unwrap();
+        assert_eq!(out, b":0100000001FE\n:020002000304F5\n:00000001FF\n");
+    }
+}
@@ -0,0 +1,558 @@
+use cursive::event::{Event, Key};
+use itertools::Itertools;
+
+use DisplayState;
+
+/// An action of a [`HexView`] which can be bound to keys in a [`Keymap`].
+///
+/// [`HexView`]: struct.HexView.html
+/// [`Keymap`]: struct.Keymap.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    /// Move the cursor to the previous nibble
+    MoveLeft,
+    /// Move the cursor to the next nibble
+    MoveRight,
+    /// Move the cursor to the previous line
+    MoveUp,
+    /// Move the cursor to the next line
+    MoveDown,
+    /// Move the cursor to the beginning of the current line
+    LineStart,
+    /// Move the cursor to the end of the current line
+    LineEnd,
+    /// Move the cursor to the first nibble of the view
+    ViewStart,
+    /// Move the cursor to the last nibble of the view
+    ViewEnd,
+    /// Move the cursor up by the number of visible rows
+    PageUp,
+    /// Move the cursor down by the number of visible rows
+    PageDown,
+    /// Move the cursor to the start of the current or previous byte group
+    PrevGroup,
+    /// Move the cursor to the start of the next byte group
+    NextGroup,
+    /// Move the cursor to the first byte of the data
+    DataStart,
+    /// Move the cursor to the last byte of the data
+    DataEnd,
+    /// Move the cursor to the next byte which is not `0`, unknown bytes are skipped
+    NextNonZero,
+    /// Move the cursor to the previous byte which is not `0`, unknown bytes are skipped
+    PrevNonZero,
+    /// Move the cursor to the next byte which differs from the byte under the cursor, unknown bytes are skipped
+    ///
+    /// If the byte under the cursor is unknown, this moves to the next known byte.
+    NextDifferent,
+    /// Move the cursor to the previous byte which differs from the byte under the cursor, unknown bytes are skipped
+    ///
+    /// If the byte under the cursor is unknown, this moves to the previous known byte.
+    PrevDifferent,
+    /// Append a `0` to the data
+    Grow,
+    /// Remove the last byte of the data
+    Shrink,
+    /// Set the nibble under the cursor to the given value and move to the next nibble.
+    ///
+    /// In insert mode, a new byte is inserted when the cursor is on the high nibble.
+    SetNibble(u8),
+    /// Set the nibble under the cursor to the value of the next hex digit typed, without moving the cursor
+    Replace,
+    /// Remove the byte under the cursor
+    Delete,
+    /// Switch between insert and overwrite mode
+    ToggleInsert,
+    /// Revert the last edit
+    Undo,
+    /// Restore the last reverted edit
+    Redo,
+    /// Ask for a pattern to search, see [`set_on_command`](struct.HexView.html#method.set_on_command)
+    Search,
+    /// Move the cursor to the next occurrence of the last search pattern
+    FindNext,
+    /// Move the cursor to the previous occurrence of the last search pattern
+    FindPrev,
+    /// Ask for an address to jump to, see [`set_on_command`](struct.HexView.html#method.set_on_command)
+    Goto,
+    /// Set a bookmark at the cursor, named by the next letter typed
+    SetBookmark,
+    /// Move the cursor to the bookmark named by the next letter typed
+    JumpToBookmark,
+    /// Move the cursor back to where it was before the last jump, see
+    /// [`jump_to`](struct.HexView.html#method.jump_to)
+    JumpBack,
+    /// Revert the last [`JumpBack`](#variant.JumpBack)
+    JumpForward,
+    /// Move the cursor to the address the bytes at the cursor point to, see
+    /// [`follow_pointer`](struct.HexView.html#method.follow_pointer)
+    FollowPointer,
+    /// Show a dialog which lists the keys of the active keymap
+    Help,
+}
+
+impl Action {
+    /// Checks whether this action alters the data, which is only allowed in the `Editable` state.
+    #[must_use]
+    pub const fn is_edit(self) -> bool {
+        matches!(
+            self,
+            Self::Grow
+                | Self::Shrink
+                | Self::SetNibble(_)
+                | Self::Replace
+                | Self::Delete
+                | Self::ToggleInsert
+                | Self::Undo
+                | Self::Redo
+        )
+    }
+
+    /// Returns a short description of this action, as shown in the help dialog.
+    ///
+    /// All [`SetNibble`](#variant.SetNibble) actions share the same description.
+    #[must_use]
+    pub const fn description(self) -> &'static str {
+        match self {
+            Self::MoveLeft => "Move to the previous nibble",
+            Self::MoveRight => "Move to the next nibble",
+            Self::MoveUp => "Move to the previous line",
+            Self::MoveDown => "Move to the next line",
+            Self::LineStart => "Move to the start of the line",
+            Self::LineEnd => "Move to the end of the line",
+            Self::ViewStart => "Move to the start of the view",
+            Self::ViewEnd => "Move to the end of the view",
+            Self::PageUp => "Move up by one page",
+            Self::PageDown => "Move down by one page",
+            Self::PrevGroup => "Move to the previous byte group",
+            Self::NextGroup => "Move to the next byte group",
+            Self::DataStart => "Move to the first byte",
+            Self::DataEnd => "Move to the last byte",
+            Self::NextNonZero => "Move to the next non-zero byte",
+            Self::PrevNonZero => "Move to the previous non-zero byte",
+            Self::NextDifferent => "Move to the next different byte",
+            Self::PrevDifferent => "Move to the previous different byte",
+            Self::Grow => "Append a byte",
+            Self::Shrink => "Remove the last byte",
+            Self::SetNibble(_) => "Set the nibble under the cursor",
+            Self::Replace => "Replace the nibble with the next digit",
+            Self::Delete => "Remove the byte under the cursor",
+            Self::ToggleInsert => "Switch between insert and overwrite",
+            Self::Undo => "Undo the last edit",
+            Self::Redo => "Redo the last undone edit",
+            Self::Search => "Search for a byte pattern",
+            Self::FindNext => "Find the next match",
+            Self::FindPrev => "Find the previous match",
+            Self::Goto => "Jump to an address",
+            Self::SetBookmark => "Set a bookmark with the next letter",
+            Self::JumpToBookmark => "Jump to the bookmark of the next letter",
+            Self::JumpBack => "Go back to the position before the last jump",
+            Self::JumpForward => "Go forward again in the jump list",
+            Self::FollowPointer => "Follow the pointer at the cursor",
+            Self::Help => "Show this help",
+        }
+    }
+}
+
+/// The result of looking up a sequence of events in a keymap
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Lookup {
+    /// The sequence is bound to the action
+    Action(Action),
+    /// The sequence is the start of at least one longer binding
+    Prefix,
+    /// Nothing is bound to the sequence
+    Unbound,
+}
+
+/// Maps key sequences to the [`Action`]s of a [`HexView`].
+///
+/// A binding consists of one or more events which have to be pressed one after another, like `g` `g` in vim. If a
+/// sequence is bound itself, longer sequences starting with it can never be reached.
+///
+/// Besides the [`default`](#method.default) keymap, there are presets for [`vim`](#method.vim) and
+/// [`emacs`](#method.emacs) users, which can be altered like any other keymap.
+///
+/// [`Action`]: enum.Action.html
+/// [`HexView`]: struct.HexView.html
+///
+/// # Examples
+///
+/// ```
+/// # extern crate cursive;
+/// # extern crate cursive_hexview;
+/// # use cursive::event::{Event, Key};
+/// # use cursive_hexview::{Action, HexView, Keymap};
+/// # fn main() {
+/// let mut keymap = Keymap::vim();
+/// keymap.bind(Key::Tab, Action::NextGroup);
+/// keymap.bind_sequence(vec![Event::Char('z'), Event::Char('z')], Action::DataStart);
+/// keymap.unbind('x');
+///
+/// let view = HexView::new().with_keymap(keymap);
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Keymap {
+    bindings: Vec<(Vec<Event>, Action)>,
+}
+
+impl Default for Keymap {
+    /// Creates the keymap which is used by a new `HexView`, see the [crate documentation](index.html).
+    fn default() -> Self {
+        let mut keymap = Self::new();
+        keymap
+            .bind(Key::Left, Action::MoveLeft)
+            .bind(Key::Right, Action::MoveRight)
+            .bind(Key::Up, Action::MoveUp)
+            .bind(Key::Down, Action::MoveDown)
+            .bind(Key::Home, Action::LineStart)
+            .bind(Key::End, Action::LineEnd)
+            .bind(Event::Shift(Key::Home), Action::ViewStart)
+            .bind(Event::Shift(Key::End), Action::ViewEnd)
+            .bind(Key::PageUp, Action::PageUp)
+            .bind(Key::PageDown, Action::PageDown)
+            .bind(Event::Ctrl(Key::Left), Action::PrevGroup)
+            .bind(Event::Ctrl(Key::Right), Action::NextGroup)
+            .bind(Event::Ctrl(Key::Home), Action::DataStart)
+            .bind(Event::Ctrl(Key::End), Action::DataEnd)
+            .bind(']', Action::NextNonZero)
+            .bind('[', Action::PrevNonZero)
+            .bind('}', Action::NextDifferent)
+            .bind('{', Action::PrevDifferent)
+            .bind('+', Action::Grow)
+            .bind('-', Action::Shrink)
+            .bind(Key::Del, Action::Delete)
+            .bind(Key::Ins, Action::ToggleInsert)
+            .bind(Event::CtrlChar('z'), Action::Undo)
+            .bind(Event::CtrlChar('y'), Action::Redo)
+            .bind(Event::CtrlChar('f'), Action::Search)
+            .bind(Key::F3, Action::FindNext)
+            .bind(Event::Shift(Key::F3), Action::FindPrev)
+            .bind(Event::CtrlChar('g'), Action::Goto)
+            .bind('m', Action::SetBookmark)
+            .bind('\'', Action::JumpToBookmark)
+            .bind(Event::Alt(Key::Left), Action::JumpBack)
+            .bind(Event::Alt(Key::Right), Action::JumpForward)
+            .bind('p', Action::FollowPointer)
+            .bind('?', Action::Help)
+            .bind(Key::F1, Action::Help)
+            .bind_hex_digits();
+        keymap
+    }
+}
+
+impl Keymap {
+    /// Creates a keymap without any bindings.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { bindings: Vec::new() }
+    }
+
+    /// Creates a keymap with vim-like bindings.
+    ///
+    /// The cursor is moved with `h`, `j`, `k` and `l`, `w` and `b` move by groups and `gg` and `G` to the start
+    /// and end of the data. Hex digits do not edit the data, instead `r` replaces the nibble under the cursor
+    /// with the next typed digit and `x` deletes the byte under the cursor. `u` undoes and `Ctrl+r` redoes the
+    /// last edit, `/`, `n` and `N` search, `:` jumps to an address and `?` shows the help. Like in vim, `m` and `'`
+    /// set and jump to bookmarks, `Ctrl+o` and `Tab` walk through the jump list and `p` follows a pointer.
+    #[must_use]
+    pub fn vim() -> Self {
+        let mut keymap = Self::new();
+        keymap
+            .bind('h', Action::MoveLeft)
+            .bind('l', Action::MoveRight)
+            .bind('k', Action::MoveUp)
+            .bind('j', Action::MoveDown)
+            .bind(Key::Left, Action::MoveLeft)
+            .bind(Key::Right, Action::MoveRight)
+            .bind(Key::Up, Action::MoveUp)
+            .bind(Key::Down, Action::MoveDown)
+            .bind('0', Action::LineStart)
+            .bind('^', Action::LineStart)
+            .bind('$', Action::LineEnd)
+            .bind('H', Action::ViewStart)
+            .bind('L', Action::ViewEnd)
+            .bind(Event::CtrlChar('b'), Action::PageUp)
+            .bind(Event::CtrlChar('f'), Action::PageDown)
+            .bind(Key::PageUp, Action::PageUp)
+            .bind(Key::PageDown, Action::PageDown)
+            .bind('b', Action::PrevGroup)
+            .bind('w', Action::NextGroup)
+            .bind_sequence(vec![Event::Char('g'), Event::Char('g')], Action::DataStart)
+            .bind('G', Action::DataEnd)
+            .bind(']', Action::NextNonZero)
+            .bind('[', Action::PrevNonZero)
+            .bind('}', Action::NextDifferent)
+            .bind('{', Action::PrevDifferent)
+            .bind('+', Action::Grow)
+            .bind('-', Action::Shrink)
+            .bind('r', Action::Replace)
+            .bind('x', Action::Delete)
+            .bind('u', Action::Undo)
+            .bind(Event::CtrlChar('r'), Action::Redo)
+            .bind('/', Action::Search)
+            .bind('n', Action::FindNext)
+            .bind('N', Action::FindPrev)
+            .bind(':', Action::Goto)
+            .bind('m', Action::SetBookmark)
+            .bind('\'', Action::JumpToBookmark)
+            .bind(Event::CtrlChar('o'), Action::JumpBack)
+            .bind(Key::Tab, Action::JumpForward)
+            .bind('p', Action::FollowPointer)
+            .bind('?', Action::Help)
+            .bind(Key::F1, Action::Help);
+        keymap
+    }
+
+    /// Creates a keymap with emacs-like bindings.
+    ///
+    /// The cursor is moved with `Ctrl+b`, `Ctrl+f`, `Ctrl+p` and `Ctrl+n`, `Ctrl+a` and `Ctrl+e` move to the
+    /// start and end of the line and `Alt+b` and `Alt+f` by groups. Hex digits edit the data, `Ctrl+d` deletes
+    /// the byte under the cursor and `Ctrl+x u` undoes the last edit. `Ctrl+s` searches, `Alt+g g` jumps to
+    /// an address and `F1` shows the help. `m` and `'` set and jump to bookmarks, `Alt+Left` and `Alt+Right`
+    /// walk through the jump list and `Alt+.` follows a pointer.
+    #[must_use]
+    pub fn emacs() -> Self {
+        let mut keymap = Self::new();
+        keymap
+            .bind(Event::CtrlChar('b'), Action::MoveLeft)
+            .bind(Event::CtrlChar('f'), Action::MoveRight)
+            .bind(Event::CtrlChar('p'), Action::MoveUp)
+            .bind(Event::CtrlChar('n'), Action::MoveDown)
+            .bind(Key::Left, Action::MoveLeft)
+            .bind(Key::Right, Action::MoveRight)
+            .bind(Key::Up, Action::MoveUp)
+            .bind(Key::Down, Action::MoveDown)
+            .bind(Event::CtrlChar('a'), Action::LineStart)
+            .bind(Event::CtrlChar('e'), Action::LineEnd)
+            .bind(Key::Home, Action::LineStart)
+            .bind(Key::End, Action::LineEnd)
+            .bind(Event::AltChar('v'), Action::PageUp)
+            .bind(Event::CtrlChar('v'), Action::PageDown)
+            .bind(Key::PageUp, Action::PageUp)
+            .bind(Key::PageDown, Action::PageDown)
+            .bind(Event::AltChar('b'), Action::PrevGroup)
+            .bind(Event::AltChar('f'), Action::NextGroup)
+            .bind(Event::AltChar('<'), Action::DataStart)
+            .bind(Event::AltChar('>'), Action::DataEnd)
+            .bind(Event::AltChar('n'), Action::NextNonZero)
+            .bind(Event::AltChar('p'), Action::PrevNonZero)
+            .bind('+', Action::Grow)
+            .bind('-', Action::Shrink)
+            .bind(Event::CtrlChar('d'), Action::Delete)
+            .bind(Key::Del, Action::Delete)
+            .bind(Key::Ins, Action::ToggleInsert)
+            .bind_sequence(vec![Event::CtrlChar('x'), Event::Char('u')], Action::Undo)
+            .bind_sequence(vec![Event::CtrlChar('x'), Event::Char('r')], Action::Redo)
+            .bind(Event::CtrlChar('s'), Action::Search)
+            .bind(Event::AltChar('s'), Action::FindNext)
+            .bind(Event::AltChar('r'), Action::FindPrev)
+            .bind_sequence(vec![Event::AltChar('g'), Event::Char('g')], Action::Goto)
+            .bind('m', Action::SetBookmark)
+            .bind('\'', Action::JumpToBookmark)
+            .bind(Event::Alt(Key::Left), Action::JumpBack)
+            .bind(Event::Alt(Key::Right), Action::JumpForward)
+            .bind(Event::AltChar('.'), Action::FollowPointer)
+            .bind(Key::F1, Action::Help)
+            .bind_hex_digits();
+        keymap
+    }
+
+    /// Binds `event` to `action`, replacing any previous binding of `event`.
+    pub fn bind<E: Into<Event>>(&mut self, event: E, action: Action) -> &mut Self {
+        self.bind_sequence(Some(event.into()), action)
+    }
+
+    /// Binds a sequence of events to `action`, replacing any previous binding of the same sequence.
+    ///
+    /// An empty sequence is ignored.
+    pub fn bind_sequence<I: IntoIterator<Item = Event>>(&mut self, events: I, action: Action) -> &mut Self {
+        let events: Vec<_> = events.into_iter().collect();
+        if !events.is_empty() {
+            self.bindings.retain(|(e, _)| *e != events);
+            self.bindings.push((events, action));
+        }
+        self
+    }
+
+    /// Removes the binding of `event`.
+    pub fn unbind<E: Into<Event>>(&mut self, event: E) -> &mut Self {
+        self.unbind_sequence(Some(event.into()))
+    }
+
+    /// Removes the binding of a sequence of events.
+    pub fn unbind_sequence<I: IntoIterator<Item = Event>>(&mut self, events: I) -> &mut Self {
+        let events: Vec<_> = events.into_iter().collect();
+        self.bindings.retain(|(e, _)| *e != events);
+        self
+    }
+
+    /// Removes all bindings of `action`.
+    pub fn unbind_action(&mut self, action: Action) -> &mut Self {
+        self.bindings.retain(|(_, a)| *a != action);
+        self
+    }
+
+    /// Returns all bindings in the order they were added.
+    pub fn bindings(&self) -> impl Iterator<Item = (&[Event], Action)> {
+        self.bindings
+            .iter()
+            .map(|(events, action)| (events.as_slice(), *action))
+    }
+
+    /// Lists the keys and what they do, for a view in `state`.
+    ///
+    /// Each entry holds the keys of one action, separated by commas, and its
+    /// [`description`](enum.Action.html#method.description). Edit actions are only listed for editable views.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cursive_hexview::{DisplayState, Keymap};
+    /// let help = Keymap::vim().help(DisplayState::Enabled);
+    /// assert_eq!(help[0], ("h, Left".to_string(), "Move to the previous nibble"));
+    ///
+    /// let help = Keymap::default().help(DisplayState::Editable);
+    /// assert!(help.contains(&("0-9, a-f, A-F".to_string(), "Set the nibble under the cursor")));
+    /// ```
+    #[must_use]
+    pub fn help(&self, state: DisplayState) -> Vec<(String, &'static str)> {
+        let mut entries: Vec<(Vec<&[Event]>, &'static str)> = Vec::new();
+        for (events, action) in self.bindings() {
+            let allowed = match state {
+                DisplayState::Disabled => false,
+                DisplayState::Enabled => !action.is_edit(),
+                DisplayState::Editable => true,
+            };
+            if !allowed {
+                continue;
+            }
+            let description = action.description();
+            match entries.iter_mut().find(|(_, d)| *d == description) {
+                Some((keys, _)) => keys.push(events),
+                None => entries.push((vec![events], description)),
+            }
+        }
+        entries
+            .into_iter()
+            .map(|(keys, description)| (format_keys(&keys), description))
+            .collect()
+    }
+
+    /// Binds `0-9`, `a-f` and `A-F` to set the nibble under the cursor
+    fn bind_hex_digits(&mut self) -> &mut Self {
+        for c in ('0'..='9').chain('a'..='f').chain('A'..='F') {
+            let value = c.to_digit(16).expect("c is a hex digit") as u8;
+            self.bind(c, Action::SetNibble(value));
+        }
+        self
+    }
+
+    /// Looks up the events pressed so far
+    pub(crate) fn lookup(&self, events: &[Event]) -> Lookup {
+        if let Some((_, action)) = self.bindings.iter().find(|(e, _)| e == events) {
+            Lookup::Action(*action)
+        } else if self.bindings.iter().any(|(e, _)| e.starts_with(events)) {
+            Lookup::Prefix
+        } else {
+            Lookup::Unbound
+        }
+    }
+}
+
+/// Joins the names of several key sequences, runs of at least three consecutive characters are shortened to `a-z`
+fn format_keys(keys: &[&[Event]]) -> String {
+    let mut parts = Vec::new();
+    let mut i = 0;
+    while i < keys.len() {
+        let mut end = i + 1;
+        if let [Event::Char(first)] = keys[i] {
+            while let Some([Event::Char(c)]) = keys.get(end) {
+                if *c as u32 != *first as u32 + (end - i) as u32 {
+                    break;
+                }
+                end += 1;
+            }
+            if end - i >= 3 {
+                if let [Event::Char(last)] = keys[end - 1] {
+                    parts.push(format!("{first}-{last}"));
+                }
+                i = end;
+                continue;
+            }
+            end = i + 1;
+        }
+        parts.push(keys[i].iter().map(format_event).join(" "));
+        i = end;
+    }
+    parts.join(", ")
+}
+
+/// Returns a readable name of `event`, e.g. `Ctrl+x`
+fn format_event(event: &Event) -> String {
+    match event {
+        Event::Char(' ') => "Space".to_string(),
+        Event::Char(c) => c.to_string(),
+        Event::CtrlChar(c) => format!("Ctrl+{c}"),
+        Event::AltChar(c) => format!("Alt+{c}"),
+        Event::Key(k) => format!("{k:?}"),
+        Event::Shift(k) => format!("Shift+{k:?}"),
+        Event::Alt(k) => format!("Alt+{k:?}"),
+        Event::AltShift(k) => format!("Alt+Shift+{k:?}"),
+        Event::Ctrl(k) => format!("Ctrl+{k:?}"),
+        Event::CtrlShift(k) => format!("Ctrl+Shift+{k:?}"),
+        Event::CtrlAlt(k) => format!("Ctrl+Alt+{k:?}"),
+        e => format!("{e:?}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lookup_sequences() {
+        let mut keymap = Keymap::new();
+        keymap.bind_sequence(vec![Event::Char('g'), Event::Char('g')], Action::DataStart);
+        keymap.bind_sequence(None, Action::DataEnd);
+
+        assert_eq!(keymap.lookup(&[Event::Char('g')]), Lookup::Prefix);
+        assert_eq!(
+            keymap.lookup(&[Event::Char('g'), Event::Char('g')]),
+            Lookup::Action(Action::DataStart)
+        );
+        assert_eq!(keymap.lookup(&[Event::Char('g'), Event::Char('x')]), Lookup::Unbound);
+        assert_eq!(keymap.bindings().count(), 1);
+    }
+
+    #[test]
+    fn rebind_and_unbind() {
+        let mut keymap = Keymap::new();
+        keymap
+            .bind('x', Action::Delete)
+            .bind('d', Action::Delete)
+            .bind('x', Action::Undo);
+        assert_eq!(keymap.lookup(&[Event::Char('x')]), Lookup::Action(Action::Undo));
+
+        keymap.unbind_action(Action::Delete);
+        assert_eq!(keymap.lookup(&[Event::Char('d')]), Lookup::Unbound);
+        keymap.unbind('x');
+        assert_eq!(keymap.bindings().count(), 0);
+    }
+
+    #[test]
+    fn format_key_runs() {
+        let keys: Vec<Vec<Event>> = vec![
+            vec![Event::Char('a')],
+            vec![Event::Char('b')],
+            vec![Event::Char('c')],
+            vec![Event::Char('x')],
+            vec![Event::Char('y')],
+            vec![Event::AltChar('g'), Event::Char(' ')],
+            vec![Event::Ctrl(Key::Left)],
+        ];
+        let keys: Vec<&[Event]> = keys.iter().map(Vec::as_slice).collect();
+        assert_eq!(format_keys(&keys), "a-c, x, y, Alt+g Space, Ctrl+Left");
+    }
+}
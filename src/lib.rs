@@ -14,36 +14,107 @@
 //!
 //! It is meant to display a data of u8 and format them like e.g. hexdump does.
 //! You can interact with the view with your keyboard.
-//! Currently the following keys are implemented:
+//! The keys can be rebound with a [`Keymap`](struct.Keymap.html), which also ships vim- and emacs-like presets.
+//! The default keymap binds the following keys:
 //!
-//! | Key                                 | Action                                                                                                                                                                                                                                                 |
-//! |-------------------------------------|--------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------|
-//! | <kbd>&leftarrow;</kbd>              | Move the cursor to the next, left nibble. If already on the left edge of the view, the event will be "ignored", which means the outer view will handle the event (e.g. focus the view next to this one)                                                |
-//! | <kbd>&rightarrow;</kbd>             | Move the cursor to the next, right nibble. If already on the right edge of the view, the event will be "ignored", which means the outer view will handle the event (e.g. focus the view next to this one)                                              |
-//! | <kbd>&uparrow;</kbd>                | Move the cursor to the previous line. If already on the top edge of the view, the event will be "ignored", which means the outer view will handle the event (e.g. focus the view next to this one)                                                     |
-//! | <kbd>&downarrow;</kbd>              | Move the cursor to the next line. If already on the bottom edge of the,view, the event will be "ignored", which means the outer view will handle the event (e.g. focus the view next to this one)                                                      |
-//! | <kbd>Home</kbd>                     | Move the cursor to the beginning of the current line.                                                                                                                                                                                                  |
-//! | <kbd>End</kbd>                      | Move the cursor to the end of the current line.                                                                                                                                                                                                        |
-//! | <kbd>Shift</kbd> + <kbd>Home</kbd>  | Move the cursor to position (0 ,0) which means to the beginning of the view.                                                                                                                                                                           |
-//! | <kbd>Shift</kbd> + <kbd>End</kbd>   | Move the cursor to the last nibble in the view.                                                                                                                                                                                                        |
-//! | <kbd>+</kbd>                        | Increase the amount of data by one byte. It will be filled up with `0`.                                                                                                                                                                                |
-//! | <kbd>-</kbd>                        | Decrease the amount of data by one. Any data that will leave the viewable area, will be permanantly lost.                                                                                                                                              |
-//! | <kbd>0-9</kbd>, <kbd>a-f</kbd>      | Set the nibble under the cursor to the corresponding hex value. Note, that this is only available in the editable state, see [`DisplayState`](enum.DisplayState.html#Editable) and [`set_display_state`](struct.HexView.html#method.set_display_state) |
+//! | Key                                        | Action                                                                                                                                                                                                                                                 |
+//! |--------------------------------------------|--------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------------|
+//! | <kbd>&leftarrow;</kbd>                     | Move the cursor to the next, left nibble. If already on the left edge of the view, the event will be "ignored", which means the outer view will handle the event (e.g. focus the view next to this one)                                                |
+//! | <kbd>&rightarrow;</kbd>                    | Move the cursor to the next, right nibble. If already on the right edge of the view, the event will be "ignored", which means the outer view will handle the event (e.g. focus the view next to this one)                                              |
+//! | <kbd>&uparrow;</kbd>                       | Move the cursor to the previous line. If already on the top edge of the view, the event will be "ignored", which means the outer view will handle the event (e.g. focus the view next to this one)                                                     |
+//! | <kbd>&downarrow;</kbd>                     | Move the cursor to the next line. If already on the bottom edge of the,view, the event will be "ignored", which means the outer view will handle the event (e.g. focus the view next to this one)                                                      |
+//! | <kbd>Home</kbd>                            | Move the cursor to the beginning of the current line.                                                                                                                                                                                                  |
+//! | <kbd>End</kbd>                             | Move the cursor to the end of the current line.                                                                                                                                                                                                        |
+//! | <kbd>Shift</kbd> + <kbd>Home</kbd>         | Move the cursor to position (0 ,0) which means to the beginning of the view.                                                                                                                                                                           |
+//! | <kbd>Shift</kbd> + <kbd>End</kbd>          | Move the cursor to the last nibble in the view.                                                                                                                                                                                                        |
+//! | <kbd>PageUp</kbd>                          | Move the cursor up by the number of visible rows.                                                                                                                                                                                                      |
+//! | <kbd>PageDown</kbd>                        | Move the cursor down by the number of visible rows.                                                                                                                                                                                                    |
+//! | <kbd>Ctrl</kbd> + <kbd>&leftarrow;</kbd>   | Move the cursor to the start of the current byte group, or to the start of the previous group if it is already there.                                                                                                                                  |
+//! | <kbd>Ctrl</kbd> + <kbd>&rightarrow;</kbd>  | Move the cursor to the start of the next byte group.                                                                                                                                                                                                   |
+//! | <kbd>Ctrl</kbd> + <kbd>Home</kbd>          | Move the cursor to the first byte of the data.                                                                                                                                                                                                         |
+//! | <kbd>Ctrl</kbd> + <kbd>End</kbd>           | Move the cursor to the last byte of the data.                                                                                                                                                                                                          |
+//! | <kbd>]</kbd>, <kbd>[</kbd>                 | Move the cursor to the next or previous byte which is not `0`.                                                                                                                                                                                         |
+//! | <kbd>}</kbd>, <kbd>{</kbd>                 | Move the cursor to the next or previous byte which differs from the byte under the cursor.                                                                                                                                                             |
+//! | <kbd>+</kbd>                               | Increase the amount of data by one byte. It will be filled up with `0`.                                                                                                                                                                                |
+//! | <kbd>-</kbd>                               | Decrease the amount of data by one. Any data that will leave the viewable area, will be permanantly lost.                                                                                                                                              |
+//! | <kbd>0-9</kbd>, <kbd>a-f</kbd>             | Set the nibble under the cursor to the corresponding hex value. Note, that this is only available in the editable state, see [`DisplayState`](enum.DisplayState.html#Editable) and [`set_display_state`](struct.HexView.html#method.set_display_state) |
+//! | <kbd>Del</kbd>                             | Remove the byte under the cursor.                                                                                                                                                                                                                      |
+//! | <kbd>Ins</kbd>                             | Switch between overwrite and insert mode, see [`set_insert_mode`](struct.HexView.html#method.set_insert_mode).                                                                                                                                         |
+//! | <kbd>Ctrl</kbd> + <kbd>z</kbd>             | Undo the last edit.                                                                                                                                                                                                                                    |
+//! | <kbd>Ctrl</kbd> + <kbd>y</kbd>             | Redo the last undone edit.                                                                                                                                                                                                                             |
+//! | <kbd>Ctrl</kbd> + <kbd>f</kbd>             | Ask for a search pattern, see [`set_on_command`](struct.HexView.html#method.set_on_command).                                                                                                                                                           |
+//! | <kbd>F3</kbd>                              | Move the cursor to the next occurrence of the last search pattern.                                                                                                                                                                                     |
+//! | <kbd>Shift</kbd> + <kbd>F3</kbd>           | Move the cursor to the previous occurrence of the last search pattern.                                                                                                                                                                                 |
+//! | <kbd>Ctrl</kbd> + <kbd>g</kbd>             | Ask for an address to jump to, see [`set_on_command`](struct.HexView.html#method.set_on_command).                                                                                                                                                      |
+//! | <kbd>m</kbd>                               | Set a bookmark at the cursor, named by the next letter, see [`add_bookmark`](struct.HexView.html#method.add_bookmark).                                                                                                                                 |
+//! | <kbd>'</kbd>                               | Move the cursor to the bookmark named by the next letter.                                                                                                                                                                                              |
+//! | <kbd>Alt</kbd> + <kbd>&leftarrow;</kbd>    | Move the cursor back to where it was before the last jump, see [`jump_to`](struct.HexView.html#method.jump_to).                                                                                                                                        |
+//! | <kbd>Alt</kbd> + <kbd>&rightarrow;</kbd>   | Revert the last move back in the jump list.                                                                                                                                                                                                            |
+//! | <kbd>p</kbd>                               | Follow the pointer at the cursor, see [`follow_pointer`](struct.HexView.html#method.follow_pointer).                                                                                                                                                   |
+//! | <kbd>?</kbd>, <kbd>F1</kbd>                | Show a dialog which lists the keys of the active keymap, see [`help`](struct.HexView.html#method.help).                                                                                                                                                |
+//! | Click                                      | Move the cursor to the clicked nibble in the hex column or to the clicked byte in the text column.                                                                                                                                                     |
+//! | Drag                                       | Select the bytes between the start and the end of the drag, see [`selection`](struct.HexView.html#method.selection).                                                                                                                                   |
+//! | Double click                               | Select the byte group under the mouse.                                                                                                                                                                                                                 |
+//! | Mouse wheel                                | Scroll up or down without moving the cursor.                                                                                                                                                                                                           |
 
 extern crate cursive_core as cursive;
 extern crate itertools;
-
-use std::borrow::Borrow;
+extern crate unicode_width;
+
+mod bookmark;
+mod column;
+mod dump;
+mod editor;
+mod history;
+mod ihex;
+mod keymap;
+mod parse;
+mod pointer;
+#[cfg(target_os = "linux")]
+mod procmem;
+mod region;
+mod source;
+mod srec;
+mod status;
+mod stream;
+
+use std::borrow::{Borrow, Cow};
 use std::cmp::min;
+use std::collections::{BTreeMap, HashMap};
 
+use bookmark::JumpList;
 use cursive::direction::Direction;
-use cursive::event::{Event, EventResult, Key, MouseEvent};
+use cursive::event::{Event, EventResult, MouseButton, MouseEvent};
 use cursive::theme::{ColorStyle, Effect};
 use cursive::vec::Vec2;
-use cursive::view::{CannotFocus, View};
-use cursive::{Printer, With};
+use cursive::view::{CannotFocus, Scrollable, View};
+use cursive::views::{Dialog, TextView};
+use cursive::{Cursive, Printer, Rect, With};
+use history::{Edit, History};
 use itertools::Itertools;
+use keymap::Lookup;
+use region::{normalize_regions, Row, RowMap};
+use source::{Polling, DEFAULT_POLL_INTERVAL};
+use std::error::Error;
 use std::fmt::{self, Write};
+use std::mem;
+use std::ops::{Range, RangeInclusive};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+use unicode_width::UnicodeWidthStr;
+
+pub use column::{Column, ColumnKind, ColumnRenderer};
+pub use dump::{Dump, DumpFormat, EndAddress};
+pub use editor::HexEditorView;
+pub use keymap::{Action, Keymap};
+pub use parse::{ParseError, ParseErrorKind};
+pub use pointer::{PointerFormat, PointerWidth};
+#[cfg(target_os = "linux")]
+pub use procmem::{Mapping, ProcessMemory};
+pub use region::{Region, RegionError};
+pub use source::{DataSource, SimulatedSource};
+pub use status::{HexStatus, HexStatusView};
+pub use stream::DataSender;
 
 /// Controls the possible interactions with a [`HexView`].
 ///
@@ -62,48 +133,110 @@ pub enum DisplayState {
     Editable,
 }
 
+/// Controls whether a [`HexView`] adapts the number of bytes per line to the available width.
+///
+/// When fitting, the view picks the largest number of bytes per line for which all columns fit into the width
+/// given by the parent view. The configured `bytes_per_line` is only used until the view has been laid out
+/// for the first time. If even the smallest line does not fit, the smallest line is used.
+///
+/// [`HexView`]: struct.HexView.html
+///
+/// # Examples
+///
+/// ```
+/// # use cursive_hexview::{FitWidth, HexViewConfig};
+/// let config = HexViewConfig {
+///     bytes_per_group: 4,
+///     fit_width: FitWidth::Groups,
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FitWidth {
+    /// Always use the configured `bytes_per_line`
+    Off,
+    /// Use a multiple of `bytes_per_group`, so that all groups are complete
+    Groups,
+    /// Use a power of two
+    PowersOfTwo,
+}
+
 /// Controls the visual output of the `HexView` struct.
 ///
 /// There are various options which can be altered. For a detailed description of them, please see the fields below.
 /// For the changes to apply, you need to use [`set_config`].
 ///
+/// Some of the options depend on each other, use [`validate`] to check them. A view which is given an invalid
+/// config clamps the offending values while rendering.
+///
+/// The separators can be any text, their width is measured in terminal columns, so e.g. box drawing characters
+/// like `│` are fine.
+///
 /// [`set_config`]: struct.HexView.html#method.set_config
-#[derive(Debug, Clone, Copy)]
+/// [`validate`]: #method.validate
+///
+/// # Examples
+///
+/// ```
+/// # use cursive_hexview::HexViewConfig;
+/// let separator = String::from(" │ ");
+/// let config = HexViewConfig {
+///     hex_ascii_separator: separator.into(),
+///     addr_hex_separator: "│".into(),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone)]
 pub struct HexViewConfig {
     /// Controls the number of bytes per line.
     ///
     /// It needs to be greater than 0 and equal or higher than the `bytes_per_group` value.
+    /// Ignored once the view has been laid out, if `fit_width` is not `FitWidth::Off`.
     /// Default is `16`
     pub bytes_per_line: usize,
     /// Controls the number of bytes per group.
     ///
     /// It needs to be greater than 0 equal or lower than `bytes_per_line`.
+    /// Groups start at the beginning of each line, so if `bytes_per_line` is not a multiple of this value,
+    /// the last group of each line is shorter than the others.
     /// Default is `1`
     pub bytes_per_group: usize,
     /// Controls the separator between the hex groups in the data output.
     ///
     /// Default is ` ` (0x20)
-    pub byte_group_separator: &'static str,
+    pub byte_group_separator: Cow<'static, str>,
     /// Controls the separator between the address label and the hex output of the data.
     ///
     /// Default is `: `
-    pub addr_hex_separator: &'static str,
+    pub addr_hex_separator: Cow<'static, str>,
     /// Controls the separator between the hex output and the ASCII representation of the data.
     ///
     /// Default is ` | `
-    pub hex_ascii_separator: &'static str,
+    pub hex_ascii_separator: Cow<'static, str>,
     /// Controls if the ASCII representation of the data should be shown.
     ///
+    /// If `false`, all [`Text`](enum.ColumnKind.html#variant.Text) columns are hidden.
     /// Default is `true`
     pub show_ascii: bool,
     /// Controls the address of the first byte
     ///
+    /// Addresses are always 64 bit wide, independent of the pointer width of the host.
     /// Default is 0
-    pub start_addr: usize,
+    pub start_addr: u64,
     /// Controls number of bytes used for displaying address.
     /// When 0, the value is computed automatically.
     /// Default is 0
     pub bytes_per_addr: usize,
+    /// Controls which columns are displayed and in which order, see [`Column`](struct.Column.html).
+    ///
+    /// Default is an address, a hex and a text column
+    pub columns: Vec<Column>,
+    /// Controls if the number of bytes per line adapts to the available width, see [`FitWidth`].
+    ///
+    /// Default is `FitWidth::Off`
+    ///
+    /// [`FitWidth`]: enum.FitWidth.html
+    pub fit_width: FitWidth,
 }
 
 impl Default for HexViewConfig {
@@ -111,22 +244,127 @@ impl Default for HexViewConfig {
         Self {
             bytes_per_line: 16,
             bytes_per_group: 1,
-            byte_group_separator: " ",
-            addr_hex_separator: ": ",
-            hex_ascii_separator: " | ",
+            byte_group_separator: " ".into(),
+            addr_hex_separator: ": ".into(),
+            hex_ascii_separator: " | ".into(),
             show_ascii: true,
             start_addr: 0,
             bytes_per_addr: 0,
+            columns: vec![
+                Column::new(ColumnKind::Addr),
+                Column::new(ColumnKind::Hex),
+                Column::new(ColumnKind::Text),
+            ],
+            fit_width: FitWidth::Off,
+        }
+    }
+}
+
+impl HexViewConfig {
+    /// Checks whether the options of this config are consistent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cursive_hexview::{ConfigError, HexViewConfig};
+    /// assert!(HexViewConfig::default().validate().is_ok());
+    ///
+    /// let config = HexViewConfig {
+    ///     bytes_per_group: 0,
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(config.validate(), Err(ConfigError::ZeroBytesPerGroup));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the first inconsistency found.
+    pub const fn validate(&self) -> Result<(), ConfigError> {
+        validate_layout(self.bytes_per_line, self.bytes_per_group)
+    }
+}
+
+/// Describes an inconsistency in a [`HexViewConfig`].
+///
+/// [`HexViewConfig`]: struct.HexViewConfig.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ConfigError {
+    /// `bytes_per_line` is 0
+    ZeroBytesPerLine,
+    /// `bytes_per_group` is 0
+    ZeroBytesPerGroup,
+    /// `bytes_per_group` is greater than `bytes_per_line`
+    GroupExceedsLine {
+        /// The configured number of bytes per group
+        bytes_per_group: usize,
+        /// The configured number of bytes per line
+        bytes_per_line: usize,
+    },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ZeroBytesPerLine => write!(f, "bytes_per_line needs to be greater than 0"),
+            Self::ZeroBytesPerGroup => write!(f, "bytes_per_group needs to be greater than 0"),
+            Self::GroupExceedsLine {
+                bytes_per_group,
+                bytes_per_line,
+            } => write!(
+                f,
+                "bytes_per_group ({bytes_per_group}) needs to be equal or lower than bytes_per_line ({bytes_per_line})"
+            ),
         }
     }
 }
 
+impl Error for ConfigError {}
+
+/// checks the relation of `bytes_per_line` and `bytes_per_group`
+const fn validate_layout(bytes_per_line: usize, bytes_per_group: usize) -> Result<(), ConfigError> {
+    if bytes_per_line == 0 {
+        Err(ConfigError::ZeroBytesPerLine)
+    } else if bytes_per_group == 0 {
+        Err(ConfigError::ZeroBytesPerGroup)
+    } else if bytes_per_group > bytes_per_line {
+        Err(ConfigError::GroupExceedsLine {
+            bytes_per_group,
+            bytes_per_line,
+        })
+    } else {
+        Ok(())
+    }
+}
+
+/// An action which takes the next typed character as its argument
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Pending {
+    /// replaces the nibble under the cursor with a hex digit
+    Replace,
+    /// sets the bookmark named by a letter
+    SetBookmark,
+    /// jumps to the bookmark named by a letter
+    JumpToBookmark,
+}
+
+/// The position of the cursor in the address space
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Cursor {
+    /// the offset of the byte under the cursor, relative to `start_addr`
+    offset: u64,
+    /// the hex digit inside the byte, 0 is the high nibble
+    digit: usize,
+}
+
 /// Hexadecimal viewer.
 ///
 /// This is a classic hexview which can be used to view and manipulate data which resides inside
 /// this struct. There are severeal states in which the view can be operatered, see [`DisplayState`].
 /// You should consider the corresponding method docs for each state.
 ///
+/// If the view is narrower than its columns, the address column stays in place, while all columns right of it
+/// scroll horizontally to follow the cursor. If it is lower than its rows, it scrolls vertically.
+///
 /// [`DisplayState`]: enum.DisplayState.html
 ///
 /// # Examples
@@ -147,12 +385,61 @@ impl Default for HexViewConfig {
 /// }
 /// ```
 pub struct HexView {
-    data: Vec<u8>,
+    regions: Vec<Region>,
     config: HexViewConfig,
-    cursor: Vec2,
+    cursor: Cursor,
     state: DisplayState,
+    /// the number of bytes per line which fitted the width of the last layout
+    fitted_bytes_per_line: Option<usize>,
+    /// the size of the last layout
+    size: Option<Vec2>,
+    /// whether the data length or the config changed since the last layout
+    layout_changed: bool,
+    /// the number of cells the columns right of the pinned address column are scrolled to the left
+    h_scroll: usize,
+    /// the number of rows which are scrolled out at the top
+    v_scroll: usize,
+    /// the first row and the number of rows on screen during the last draw, which are fewer than in the layout if
+    /// a parent like a `ScrollView` only shows a part of the view
+    viewport: Mutex<Option<(usize, usize)>>,
+    /// whether the next layout scrolls to the cursor, which is not the case after scrolling with the mouse wheel
+    follow_cursor: bool,
+    /// the offsets of the first and the last selected byte
+    selection: Option<(u64, u64)>,
+    /// the offset where the current mouse drag started
+    drag_start: Option<u64>,
+    /// the time and offset of the last click, to detect double clicks
+    last_click: Option<(Instant, u64)>,
+    keymap: Keymap,
+    /// the events of a key sequence which has been started but not completed yet
+    pending_keys: Vec<Event>,
+    /// the action which waits for the next typed character
+    pending: Option<Pending>,
+    insert_mode: bool,
+    history: History,
+    /// the pattern of the last search
+    search_pattern: Option<Vec<u8>>,
+    on_command: Option<CommandCallback>,
+    /// the status shared with all status views
+    status: Arc<Mutex<HexStatus>>,
+    /// whether appended data moves the cursor to the end
+    follow: bool,
+    /// the number of bytes after which appending drops the oldest ones
+    max_len: Option<usize>,
+    /// the source which is polled for the visible bytes
+    polling: Option<Polling>,
+    poll_interval: Duration,
+    /// the offsets of the bytes which changed during a poll, with the time they changed
+    changes: HashMap<u64, Instant>,
+    /// the addresses of the bookmarks by name
+    bookmarks: BTreeMap<char, u64>,
+    jumps: JumpList,
+    pointer_format: PointerFormat,
 }
 
+/// A callback which is run for actions which need the user interface of the application
+type CommandCallback = Arc<dyn Fn(&mut Cursive, Action) + Send + Sync>;
+
 impl Default for HexView {
     /// Creates a new, default `HexView` with an empty databuffer and disabled state.
     fn default() -> Self {
@@ -164,6 +451,7 @@ impl fmt::Debug for HexView {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("HexView")
             .field("config", &self.config)
+            .field("regions", &self.regions.len())
             .field("cursor", &self.cursor)
             .field("state", &self.state)
             .finish_non_exhaustive()
@@ -210,13 +498,64 @@ impl HexView {
     /// ```
     pub fn new_from_iter<B: Borrow<u8>, I: IntoIterator<Item = B>>(data: I) -> Self {
         Self {
-            cursor: Vec2::zero(),
-            data: data.into_iter().map(|u| *u.borrow()).collect(),
+            cursor: Cursor::default(),
+            regions: vec![Region::new("", 0, data)],
             state: DisplayState::Disabled,
             config: HexViewConfig::default(),
+            fitted_bytes_per_line: None,
+            size: None,
+            layout_changed: true,
+            h_scroll: 0,
+            v_scroll: 0,
+            viewport: Mutex::new(None),
+            follow_cursor: true,
+            selection: None,
+            drag_start: None,
+            last_click: None,
+            keymap: Keymap::default(),
+            pending_keys: Vec::new(),
+            pending: None,
+            insert_mode: false,
+            history: History::default(),
+            search_pattern: None,
+            on_command: None,
+            status: Arc::default(),
+            follow: false,
+            max_len: None,
+            polling: None,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            changes: HashMap::new(),
+            bookmarks: BTreeMap::new(),
+            jumps: JumpList::default(),
+            pointer_format: PointerFormat::default(),
         }
     }
 
+    /// Creates a new `HexView` which displays several, possibly sparse, regions and disabled state.
+    ///
+    /// See [`set_regions`](#method.set_regions) for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cursive_hexview::{HexView, Region};
+    /// let view = HexView::new_from_regions(vec![
+    ///     Region::new("flash", 0x0800_0000, vec![0xFF; 32]),
+    ///     Region::new("ram", 0x2000_0000, vec![0x00; 16]),
+    /// ])
+    /// .unwrap();
+    /// assert_eq!(view.len(), 48);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the regions overlap or do not fit into the 64 bit address space.
+    pub fn new_from_regions<I: IntoIterator<Item = Region>>(regions: I) -> Result<Self, RegionError> {
+        let mut view = Self::new();
+        view.set_regions(regions)?;
+        Ok(view)
+    }
+
     /// This function allows the customization of the `HexView` output.
     ///
     /// For options and explanation of every possible option, see the `HexViewConfig` struct.
@@ -233,6 +572,8 @@ impl HexView {
     /// ```
     pub fn set_config(&mut self, config: HexViewConfig) {
         self.config = config;
+        self.layout_changed = true;
+        self.clamp_cursor();
     }
 
     /// [`set_config`](#method.set_config)
@@ -241,8 +582,56 @@ impl HexView {
         self.with(|s| s.set_config(config))
     }
 
+    /// Like [`set_config`](#method.set_config), but rejects inconsistent configs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cursive_hexview::{ConfigError, HexView, HexViewConfig};
+    /// let mut view = HexView::new();
+    /// let config = HexViewConfig {
+    ///     bytes_per_line: 4,
+    ///     bytes_per_group: 8,
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(
+    ///     view.try_set_config(config),
+    ///     Err(ConfigError::GroupExceedsLine {
+    ///         bytes_per_group: 8,
+    ///         bytes_per_line: 4
+    ///     })
+    /// );
+    /// assert_eq!(view.config().bytes_per_line, 16);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of [`HexViewConfig::validate`], the current config is kept in this case.
+    ///
+    /// [`HexViewConfig::validate`]: struct.HexViewConfig.html#method.validate
+    pub fn try_set_config(&mut self, config: HexViewConfig) -> Result<(), ConfigError> {
+        config.validate()?;
+        self.set_config(config);
+        Ok(())
+    }
+
+    /// [`try_set_config`](#method.try_set_config)
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of [`HexViewConfig::validate`].
+    ///
+    /// [`HexViewConfig::validate`]: struct.HexViewConfig.html#method.validate
+    pub fn try_with_config(self, config: HexViewConfig) -> Result<Self, ConfigError> {
+        config.validate()?;
+        Ok(self.with_config(config))
+    }
+
     /// Returns a reference to the internal data.
     ///
+    /// If this view displays more than one region, this is the data of the region with the lowest offset.
+    /// Use [`regions`](#method.regions) to access all of them.
+    ///
     /// # Examples
     ///
     /// ```
@@ -253,12 +642,13 @@ impl HexView {
     /// ```
     #[must_use]
     pub fn data(&self) -> &[u8] {
-        self.data.as_slice()
+        self.regions[0].data()
     }
 
     /// Sets the data during the lifetime of this instance.
     ///
-    /// For insance to update the data due to an external event.
+    /// For insance to update the data due to an external event. The undo history is cleared and the data counts
+    /// as saved, see [`is_dirty`](#method.is_dirty).
     ///
     /// ```
     /// # use cursive_hexview::HexView;
@@ -266,7 +656,162 @@ impl HexView {
     /// view.set_data(b"Hello, World!".to_owned().iter());
     /// ```
     pub fn set_data<B: Borrow<u8>, I: IntoIterator<Item = B>>(&mut self, data: I) {
-        self.data = data.into_iter().map(|u| *u.borrow()).collect();
+        self.regions = vec![Region::new("", 0, data)];
+        self.history.clear(false);
+        self.reset_source_state();
+        self.layout_changed = true;
+        self.clamp_cursor();
+    }
+
+    /// Returns all regions displayed by this view, sorted by their offset.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cursive_hexview::HexView;
+    /// let view = HexView::new_from_iter(b"ABC");
+    /// assert_eq!(view.regions().len(), 1);
+    /// assert_eq!(view.regions()[0].data(), b"ABC");
+    /// ```
+    #[must_use]
+    pub fn regions(&self) -> &[Region] {
+        &self.regions
+    }
+
+    /// Replaces the data of this view with several, possibly sparse, regions.
+    ///
+    /// The offset of each region is relative to the configured `start_addr`. The address space between two
+    /// regions is unmapped: unmapped bytes which share a line with mapped bytes are displayed as `??`, all other
+    /// unmapped lines are hidden behind a single separator row, which the cursor skips.
+    ///
+    /// Empty regions are ignored. The undo history is cleared and the data counts as saved, see
+    /// [`is_dirty`](#method.is_dirty).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cursive_hexview::{HexView, Region};
+    /// let mut view = HexView::new();
+    /// view.set_regions(vec![
+    ///     Region::new("ram", 0x2000_0000, vec![0x00; 16]),
+    ///     Region::new("flash", 0x0800_0000, vec![0xFF; 32]),
+    /// ])
+    /// .unwrap();
+    /// assert_eq!(view.regions()[0].name(), "flash");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the regions overlap or do not fit into the 64 bit address space. In this case the
+    /// view is not altered.
+    pub fn set_regions<I: IntoIterator<Item = Region>>(&mut self, regions: I) -> Result<(), RegionError> {
+        self.regions = normalize_regions(regions.into_iter().collect())?;
+        self.history.clear(false);
+        self.reset_source_state();
+        self.layout_changed = true;
+        self.clamp_cursor();
+        Ok(())
+    }
+
+    /// Returns the byte at `addr`, or `None` if it is not mapped or its value is unknown.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cursive_hexview::{HexView, Region};
+    /// let view = HexView::new_from_regions(vec![Region::new_partial("disk", 0, vec![Some(0x55), None])]).unwrap();
+    /// assert_eq!(view.byte(0), Some(0x55));
+    /// assert_eq!(view.byte(1), None);
+    /// assert_eq!(view.byte(2), None);
+    /// ```
+    #[must_use]
+    pub fn byte(&self, addr: u64) -> Option<u8> {
+        self.get_byte(addr.wrapping_sub(self.config.start_addr))
+    }
+
+    /// Checks whether the byte at `addr` is mapped and its value is known.
+    ///
+    /// Unknown bytes are displayed as `??`, are never part of a search match, are skipped when moving to the
+    /// next non-zero or different byte and cannot be edited. They are created with [`Region::new_partial`] or by
+    /// a [`DataSource`] which fails to read them.
+    ///
+    /// [`Region::new_partial`]: struct.Region.html#method.new_partial
+    /// [`DataSource`]: trait.DataSource.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cursive_hexview::{HexView, Region};
+    /// let view = HexView::new_from_regions(vec![Region::new_partial("disk", 0, vec![Some(0x55), None])]).unwrap();
+    /// assert!(view.is_known(0));
+    /// assert!(!view.is_known(1));
+    /// ```
+    #[must_use]
+    pub fn is_known(&self, addr: u64) -> bool {
+        self.byte(addr).is_some()
+    }
+
+    /// Returns the address of the byte under the cursor.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cursive_hexview::{HexView, HexViewConfig};
+    /// let mut view = HexView::new_from_iter(&[0; 64]);
+    /// view.config_mut().start_addr = 0x1000;
+    /// assert_eq!(view.cursor_addr(), 0x1000);
+    ///
+    /// view.set_cursor_addr(0x1020);
+    /// assert_eq!(view.cursor_addr(), 0x1020);
+    /// ```
+    #[must_use]
+    pub fn cursor_addr(&self) -> u64 {
+        self.get_addr(self.cursor.offset)
+    }
+
+    /// Moves the cursor to the first hex digit of the byte at `addr`.
+    ///
+    /// If the byte is hidden behind a separator, the cursor moves to the start of the next displayed line. If it
+    /// lies behind the last byte of its line, the cursor moves to the end of that line.
+    pub fn set_cursor_addr(&mut self, addr: u64) {
+        self.cursor = Cursor {
+            offset: addr.wrapping_sub(self.config.start_addr),
+            digit: 0,
+        };
+        self.clamp_cursor();
+        self.scroll_to_cursor();
+    }
+
+    /// Returns the addresses of the first and the last selected byte.
+    ///
+    /// Bytes are selected by dragging with the mouse, or by double clicking a byte, which selects its group.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cursive_hexview::HexView;
+    /// let mut view = HexView::new_from_iter(&[0; 64]);
+    /// assert_eq!(view.selection(), None);
+    ///
+    /// view.set_selection(0x20..=0x10);
+    /// assert_eq!(view.selection(), Some(0x10..=0x20));
+    /// ```
+    #[must_use]
+    pub fn selection(&self) -> Option<RangeInclusive<u64>> {
+        self.selection
+            .map(|(first, last)| self.get_addr(first)..=self.get_addr(last))
+    }
+
+    /// Selects all bytes from the first to the last address of `range`, in any order.
+    pub fn set_selection(&mut self, range: RangeInclusive<u64>) {
+        let first = range.start().wrapping_sub(self.config.start_addr);
+        let last = range.end().wrapping_sub(self.config.start_addr);
+        self.selection = Some((min(first, last), std::cmp::max(first, last)));
+    }
+
+    /// Removes the selection.
+    pub fn clear_selection(&mut self) {
+        self.selection = None;
     }
 
     /// [`set_display_state`](#method.set_display_state)
@@ -290,6 +835,7 @@ impl HexView {
     /// changes visible, you must redraw this view.
     #[must_use]
     pub fn config_mut(&mut self) -> &mut HexViewConfig {
+        self.layout_changed = true;
         &mut self.config
     }
 
@@ -319,6 +865,8 @@ impl HexView {
 
     /// Returns the length of the data.
     ///
+    /// For views with several regions this is the number of mapped bytes, gaps are not counted.
+    ///
     /// # Examples
     ///
     /// ```
@@ -328,7 +876,7 @@ impl HexView {
     /// ```
     #[must_use]
     pub fn len(&self) -> usize {
-        self.data.len()
+        self.regions.iter().map(Region::len).sum()
     }
 
     /// Checks whether the data is empty.
@@ -348,7 +896,7 @@ impl HexView {
     /// ```
     #[must_use]
     pub fn is_empty(&self) -> bool {
-        self.data.is_empty()
+        self.regions.iter().all(Region::is_empty)
     }
 
     /// Sets the length of the data which this view displays.
@@ -356,6 +904,10 @@ impl HexView {
     /// If the new length is greater than the current one, 0's will be appended to the data.
     /// If the new length is less than the current one, the data will be truncated and is lost.
     ///
+    /// For views with several regions, only the last region grows. Shrinking truncates the data in address order,
+    /// regions which end up empty are removed. If the length changes, the undo history is cleared and the data
+    /// counts as modified.
+    ///
     /// # Examples
     ///
     /// ```
@@ -367,221 +919,1280 @@ impl HexView {
     /// assert_eq!(view.data(), &vec![0u8, 0u8, 0u8]);
     /// ```
     pub fn set_len(&mut self, length: usize) {
-        if self.data.len() != length {
-            let oldlen = self.data.len();
-            self.data.resize(length, 0);
-            if oldlen > length {
-                self.cursor.y = min(self.cursor.y, self.get_widget_height());
-                self.cursor.x = min(self.cursor.x, (self.data.len() * 2).saturating_sub(1));
+        let oldlen = self.len();
+        if oldlen < length {
+            let last = self.regions.last_mut().expect("there is always one region");
+            let others = oldlen - last.len();
+            let max_len = (u64::MAX - last.offset()).saturating_add(1);
+            last.resize(min((length - others) as u64, max_len) as usize);
+        } else if oldlen > length {
+            let mut remaining = length;
+            for region in &mut self.regions {
+                let len = min(region.len(), remaining);
+                region.resize(len);
+                remaining -= len;
             }
+            let regions = mem::take(&mut self.regions);
+            self.regions = normalize_regions(regions).expect("truncated regions stay valid");
+            self.clamp_cursor();
+        } else {
+            return;
         }
+        self.history.clear(true);
+        self.layout_changed = true;
     }
-}
-
-#[derive(Clone, Copy)]
-enum Field {
-    Addr,
-    AddrSep,
-    Hex,
-    AsciiSep,
-    Ascii,
-}
-
-/// calcs the position in a line with spacing
-fn get_cursor_offset(vec: Vec2, config: &HexViewConfig) -> Vec2 {
-    (
-        ((vec.x as f32 / (2 * config.bytes_per_group) as f32).floor() as usize) * config.byte_group_separator.len(),
-        0,
-    )
-        .into()
-}
-
-/// returns the number of elements in `row` for a given `datalen` with `elements_per_line`
-fn get_elements_in_row(datalen: usize, row: usize, elements_per_line: usize) -> usize {
-    min(datalen.saturating_sub(elements_per_line * row), elements_per_line)
-}
-
-/// returns the maximal cursor position in a `row` for a `datalen` and `elements_per_line`
-fn get_max_x_in_row(datalen: usize, row: usize, elements_per_line: usize) -> usize {
-    (get_elements_in_row(datalen, row, elements_per_line) * 2).saturating_sub(1)
-}
 
-/// converts the character either to itself if it `is_ascii_graphic`
-fn make_printable<T: Borrow<u8>>(c: T) -> char {
-    let c = *c.borrow();
-    if c.is_ascii_graphic() {
-        c as char
-    } else {
-        '.'
+    /// Returns the keymap which translates key events into actions.
+    #[must_use]
+    pub const fn keymap(&self) -> &Keymap {
+        &self.keymap
     }
-}
 
-// implements helper functions for this struct
-impl HexView {
-    /// Counts how many digits we need to align the addresses evenly.
+    /// Returns a mutable reference to the keymap, e.g. to rebind single keys.
     ///
-    /// E.g. we need 2 digits for 20 elements (0x14), but only 1 for 10 elements (0xA)
-    fn get_addr_digit_length(&self) -> usize {
-        match self.data.len() {
-            0..=1 => 1,
-            e => std::cmp::max(
-                ((e + self.config.start_addr) as f64).log(16.0).ceil() as usize,
-                self.config.bytes_per_addr,
-            ),
-        }
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cursive;
+    /// # extern crate cursive_hexview;
+    /// # use cursive::event::Key;
+    /// # use cursive_hexview::{Action, HexView};
+    /// # fn main() {
+    /// let mut view = HexView::new();
+    /// view.keymap_mut().bind(Key::Tab, Action::NextGroup);
+    /// # }
+    /// ```
+    pub fn keymap_mut(&mut self) -> &mut Keymap {
+        self.pending_keys.clear();
+        &mut self.keymap
     }
 
-    /// Counts how many rows we need to display the complete data
-    fn get_widget_height(&self) -> usize {
-        match self.data.len() {
-            0 => 1,
-            e => (e as f64 / self.config.bytes_per_line as f64).ceil() as usize,
-        }
+    /// Replaces the keymap which translates key events into actions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cursive_hexview::{HexView, Keymap};
+    /// let mut view = HexView::new();
+    /// view.set_keymap(Keymap::emacs());
+    /// ```
+    pub fn set_keymap(&mut self, keymap: Keymap) {
+        self.keymap = keymap;
+        self.pending_keys.clear();
     }
 
-    /// calcs the offset to the current position to match the spacing we insert to group the hex chars.
-    ///
-    /// e.g. cursor (5, 0) will result in (6, 0) because of the 1 space spacing after the fourth char
-    /// and cursor (9, 0) will result in (11, 0) because of the 1+1 spacing after the fourth and eighth char
-    fn get_cursor_offset(&self) -> Vec2 {
-        self.cursor + get_cursor_offset(self.cursor, &self.config)
+    /// [`set_keymap`](#method.set_keymap)
+    #[must_use]
+    pub fn with_keymap(self, keymap: Keymap) -> Self {
+        self.with(|s| s.set_keymap(keymap))
     }
 
-    /// gets the amount of nibbles in the current row
-    fn get_elements_in_current_row(&self) -> usize {
-        get_elements_in_row(self.data.len(), self.cursor.y, self.config.bytes_per_line)
+    /// Sets a callback which is run for actions the view cannot perform on its own.
+    ///
+    /// These are [`Action::Search`] and [`Action::Goto`], which have to ask the user for a pattern or an address,
+    /// e.g. with a dialog that calls [`search`](#method.search) or [`set_cursor_addr`](#method.set_cursor_addr).
+    /// Without a callback, their keys are ignored.
+    ///
+    /// [`Action::Search`]: enum.Action.html#variant.Search
+    /// [`Action::Goto`]: enum.Action.html#variant.Goto
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate cursive;
+    /// # extern crate cursive_hexview;
+    /// # use cursive::views::Dialog;
+    /// # use cursive_hexview::{Action, HexView};
+    /// # fn main() {
+    /// let mut view = HexView::new();
+    /// view.set_on_command(|s, action| {
+    ///     if action == Action::Goto {
+    ///         s.add_layer(Dialog::info("Where to?"));
+    ///     }
+    /// });
+    /// # }
+    /// ```
+    pub fn set_on_command<F: Fn(&mut Cursive, Action) + Send + Sync + 'static>(&mut self, callback: F) {
+        self.on_command = Some(Arc::new(callback));
     }
 
-    /// gets the max cursor-x position in the current row
-    fn get_max_x_in_current_row(&self) -> usize {
-        get_max_x_in_row(self.data.len(), self.cursor.y, self.config.bytes_per_line)
+    /// [`set_on_command`](#method.set_on_command)
+    #[must_use]
+    pub fn on_command<F: Fn(&mut Cursive, Action) + Send + Sync + 'static>(self, callback: F) -> Self {
+        self.with(|s| s.set_on_command(callback))
     }
 
-    /// advances the x position by one
+    /// Performs `action` as if one of its keys had been pressed.
     ///
-    /// Returns either an `EventResult::Ignored` if the end of
-    /// the line is reached or `EventResult::Consumed(None)` if it was successful.
-    fn cursor_x_advance(&mut self) -> EventResult {
-        let max_pos = self.get_max_x_in_current_row();
-        if self.cursor.x == max_pos {
-            return EventResult::Ignored;
+    /// This allows e.g. menus to trigger the same actions as the keyboard. The result tells whether the action
+    /// could be performed and may carry the [`on_command`](#method.set_on_command) callback.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cursive_hexview::{Action, DisplayState, HexView};
+    /// let mut view = HexView::new_from_iter(b"ABC").display_state(DisplayState::Editable);
+    /// view.perform(Action::Grow);
+    /// assert_eq!(view.data(), b"ABC\0");
+    /// view.perform(Action::Undo);
+    /// assert_eq!(view.data(), b"ABC");
+    /// ```
+    pub fn perform(&mut self, action: Action) -> EventResult {
+        let res = self.perform_action(action);
+        if res.is_consumed() {
+            self.scroll_to_cursor();
+            self.publish_status();
         }
+        res
+    }
 
-        self.cursor.x = min(self.cursor.x + 1, max_pos);
-        EventResult::Consumed(None)
+    /// Checks whether typed hex digits insert new bytes instead of overwriting the existing ones.
+    #[must_use]
+    pub const fn is_insert_mode(&self) -> bool {
+        self.insert_mode
     }
 
-    /// Gets the element under the cursor
+    /// Switches between insert and overwrite mode.
     ///
-    /// (which points to a nibble, but we are interested in the
-    /// whole u8)
+    /// In insert mode, typing a hex digit on the high nibble of a byte inserts a new byte in front of it. The
+    /// following bytes of the same region move back, so insertion fails if the region would grow into the next one.
     ///
-    /// Returns none if the cursor is out of range.
-    fn get_element_under_cursor(&self) -> Option<u8> {
-        let elem = self.cursor.y * self.config.bytes_per_line + self.cursor.x / 2;
-        self.data.get(elem).copied()
+    /// # Examples
+    ///
+    /// ```
+    /// # use cursive_hexview::{Action, DisplayState, HexView};
+    /// let mut view = HexView::new_from_iter(b"BC").display_state(DisplayState::Editable);
+    /// view.set_insert_mode(true);
+    /// view.perform(Action::SetNibble(4));
+    /// view.perform(Action::SetNibble(1));
+    /// assert_eq!(view.data(), b"ABC");
+    /// ```
+    pub fn set_insert_mode(&mut self, insert: bool) {
+        self.insert_mode = insert;
     }
 
-    /// Converts the visual position to a non spaced one.
+    /// Reverts the last edit made with the keyboard or [`perform`](#method.perform) and moves the cursor to it.
     ///
-    /// This function is used to convert the
-    /// point where the mouse clicked to the real cursor position without padding
-    fn convert_visual_to_real_cursor(&self, pos: Vec2) -> Vec2 {
-        let mut res = pos;
-        let hex_offset = self.get_field_length(Field::Addr) + self.get_field_length(Field::AddrSep);
-
-        res.y = min(self.get_widget_height() - 1, pos.y);
-        res.x = res.x.saturating_sub(hex_offset);
-        res.x = res.x.saturating_sub(get_cursor_offset(res, &self.config).x);
-        res.x = min(
-            get_max_x_in_row(self.data.len(), res.y, self.config.bytes_per_line),
-            res.x,
-        );
-
-        res
+    /// Returns `false` if there is nothing to undo.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cursive_hexview::{Action, DisplayState, HexView};
+    /// let mut view = HexView::new_from_iter(b"ABC").display_state(DisplayState::Editable);
+    /// view.perform(Action::Delete);
+    /// assert_eq!(view.data(), b"BC");
+    /// assert!(view.undo());
+    /// assert_eq!(view.data(), b"ABC");
+    /// assert!(view.redo());
+    /// assert_eq!(view.data(), b"BC");
+    /// assert!(!view.redo());
+    /// ```
+    pub fn undo(&mut self) -> bool {
+        let edit = self.history.next_undo();
+        if !self.replay(edit) {
+            return false;
+        }
+        self.history.undo();
+        true
     }
 
-    /// returns the displayed characters per field
-    fn get_field_length(&self, field: Field) -> usize {
-        match field {
-            Field::Addr => self.get_addr_digit_length(),
-            Field::AddrSep => self.config.addr_hex_separator.len(),
-            Field::Hex => {
-                (((2 * self.config.bytes_per_group) + self.config.byte_group_separator.len())
-                    * (self.config.bytes_per_line / self.config.bytes_per_group))
-                    - self.config.byte_group_separator.len()
-            }
-            Field::AsciiSep => self.config.hex_ascii_separator.len(),
-            Field::Ascii => self.config.bytes_per_line * 2,
+    /// Restores the last edit reverted by [`undo`](#method.undo) and moves the cursor to it.
+    ///
+    /// Returns `false` if there is nothing to redo.
+    pub fn redo(&mut self) -> bool {
+        let edit = self.history.next_redo();
+        if !self.replay(edit) {
+            return false;
         }
+        self.history.redo();
+        true
     }
-}
 
-// implements draw-helper functions
-// it will look as follows
-// addr: hexehex hexhex hexhex ... | asciiiiiii
-// the addr field will be padded, so that all addresses are equal in length
-// the hex field will be grouped by 4 character (nibble) and seperated by 1 space
-// the seperator is a special pipe, which is longer and connects with the lower and bottom "pipe" (BOX DRAWINGS LIGHT VERTICAL \u{2502})
-// the ascii part is just the ascii char of the coressponding hex value if it is [graphical](https://doc.rust-lang.org/std/primitive.u8.html#method.is_ascii_graphic), if not it will be displayed as a dot (.)
-impl HexView {
-    /// draws the addr field into the printer
-    fn draw_addr(&self, printer: &Printer) {
-        let digits_len = self.get_addr_digit_length();
-        for lines in 0..self.get_widget_height() {
-            printer.print(
-                (0, lines),
-                &format!(
-                    "{:0len$X}",
-                    self.config.start_addr + lines * self.config.bytes_per_line,
-                    len = digits_len
-                ),
-            );
-        }
+    /// Checks whether there is an edit which can be reverted.
+    #[must_use]
+    pub fn can_undo(&self) -> bool {
+        self.history.can_undo()
     }
 
-    fn draw_addr_hex_sep(&self, printer: &Printer) {
-        printer.print_vline((0, 0), self.get_widget_height(), self.config.addr_hex_separator);
+    /// Checks whether there is a reverted edit which can be restored.
+    #[must_use]
+    pub fn can_redo(&self) -> bool {
+        self.history.can_redo()
     }
 
-    /// draws the hex fields between the addr and ascii representation
-    fn draw_hex(&self, printer: &Printer) {
-        for (i, c) in self.data.chunks(self.config.bytes_per_line).enumerate() {
-            let hex = c
-                .chunks(self.config.bytes_per_group)
-                .map(|c| {
-                    let mut s = String::new();
-                    for &b in c {
-                        write!(&mut s, "{:02X}", b).expect("Unable to write hex values");
-                    }
-                    s
+    /// Moves the cursor to the next occurrence of `pattern`, starting at the cursor, and selects it.
+    ///
+    /// The pattern is remembered for [`find_next`](#method.find_next) and [`find_prev`](#method.find_prev).
+    /// Matches never span unmapped or unknown bytes. Returns `false` if there is no match, the cursor stays in place then.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cursive_hexview::HexView;
+    /// let mut view = HexView::new_from_iter(b"abcabc");
+    /// assert!(view.search(b"bc"));
+    /// assert_eq!(view.cursor_addr(), 1);
+    /// assert_eq!(view.selection(), Some(1..=2));
+    /// assert!(view.find_next());
+    /// assert_eq!(view.cursor_addr(), 4);
+    /// assert!(!view.find_next());
+    /// assert!(view.find_prev());
+    /// assert_eq!(view.cursor_addr(), 1);
+    /// ```
+    pub fn search(&mut self, pattern: &[u8]) -> bool {
+        self.search_pattern = Some(pattern.to_vec());
+        let found = self.find_pattern(pattern, true, self.cursor.offset);
+        self.select_match(found)
+    }
+
+    /// Moves the cursor to the next occurrence of the last [`search`](#method.search) pattern and selects it.
+    ///
+    /// Returns `false` if there is no pattern or no further match.
+    pub fn find_next(&mut self) -> bool {
+        let found = self.cursor.offset.checked_add(1).and_then(|start| {
+            let pattern = self.search_pattern.as_deref()?;
+            self.find_pattern(pattern, true, start)
+        });
+        self.select_match(found)
+    }
+
+    /// Moves the cursor to the previous occurrence of the last [`search`](#method.search) pattern and selects it.
+    ///
+    /// Returns `false` if there is no pattern or no previous match.
+    pub fn find_prev(&mut self) -> bool {
+        let found = self
+            .search_pattern
+            .as_deref()
+            .and_then(|pattern| self.find_pattern(pattern, false, self.cursor.offset));
+        self.select_match(found)
+    }
+
+    /// Lists the keys of the active keymap which are usable in the current display state, and what they do.
+    ///
+    /// [`Action::Search`] and [`Action::Goto`] are only listed if there is an
+    /// [`on_command`](#method.set_on_command) callback to handle them.
+    ///
+    /// [`Action::Search`]: enum.Action.html#variant.Search
+    /// [`Action::Goto`]: enum.Action.html#variant.Goto
+    ///
+    /// This is the content of the dialog shown by [`Action::Help`](enum.Action.html#variant.Help), see
+    /// [`Keymap::help`](struct.Keymap.html#method.help).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cursive_hexview::{DisplayState, HexView};
+    /// let view = HexView::new().display_state(DisplayState::Enabled);
+    /// assert!(view.help().iter().all(|(_, description)| *description != "Undo the last edit"));
+    /// ```
+    #[must_use]
+    pub fn help(&self) -> Vec<(String, &'static str)> {
+        let mut help = self.keymap.help(self.state);
+        if self.on_command.is_none() {
+            let commands = [Action::Search.description(), Action::Goto.description()];
+            help.retain(|(_, description)| !commands.contains(description));
+        }
+        help
+    }
+
+    /// Returns the mapped bytes of the selection, or the byte under the cursor if nothing is selected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cursive_hexview::HexView;
+    /// let mut view = HexView::new_from_iter(b"ABCD");
+    /// assert_eq!(view.selected_bytes(), b"A");
+    /// view.set_selection(1..=2);
+    /// assert_eq!(view.selected_bytes(), b"BC");
+    /// ```
+    #[must_use]
+    pub fn selected_bytes(&self) -> Vec<u8> {
+        let (first, last) = self.selection.unwrap_or((self.cursor.offset, self.cursor.offset));
+        (first..=last).filter_map(|offset| self.get_byte(offset)).collect()
+    }
+
+    /// Checks whether the data has been edited since it was set or last marked as saved.
+    ///
+    /// Undoing all edits makes the data clean again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cursive_hexview::{Action, DisplayState, HexView};
+    /// let mut view = HexView::new_from_iter(b"ABC").display_state(DisplayState::Editable);
+    /// view.perform(Action::SetNibble(5));
+    /// assert!(view.is_dirty());
+    /// view.mark_saved();
+    /// assert!(!view.is_dirty());
+    /// view.undo();
+    /// assert!(view.is_dirty());
+    /// ```
+    #[must_use]
+    pub fn is_dirty(&self) -> bool {
+        self.history.is_dirty()
+    }
+
+    /// Marks the current data as saved, e.g. after it has been written to a file.
+    pub fn mark_saved(&mut self) {
+        self.history.mark_saved();
+        self.publish_status();
+    }
+
+    /// Returns the position of the cursor and the edit state, as displayed by a [`HexStatusView`].
+    ///
+    /// [`HexStatusView`]: struct.HexStatusView.html
+    #[must_use]
+    pub fn status(&self) -> HexStatus {
+        HexStatus {
+            addr: self.cursor_addr(),
+            addr_width: self.get_addr_digit_length(),
+            byte: self.get_element_under_cursor(),
+            selection_len: self
+                .selection
+                .map_or(0, |(first, last)| (last - first).saturating_add(1)),
+            insert_mode: self.insert_mode,
+            dirty: self.is_dirty(),
+            len: self.len(),
+        }
+    }
+
+    /// Creates a status bar which shows the [`status`](#method.status) of this view.
+    ///
+    /// See [`HexStatusView`](struct.HexStatusView.html) for an example.
+    #[must_use]
+    pub fn status_view(&self) -> HexStatusView {
+        self.publish_status();
+        HexStatusView::new(Arc::clone(&self.status))
+    }
+
+    /// Returns the pattern of the last [`search`](#method.search).
+    #[must_use]
+    pub fn search_pattern(&self) -> Option<&[u8]> {
+        self.search_pattern.as_deref()
+    }
+}
+
+/// A part of a displayed line
+#[derive(Clone, Copy)]
+enum Part<'a> {
+    Separator(&'a str),
+    Column(&'a Column),
+}
+
+/// A part of a displayed line, placed at column `x` and `width` cells wide
+#[derive(Clone, Copy)]
+struct Cell<'a> {
+    x: usize,
+    width: usize,
+    part: Part<'a>,
+}
+
+impl Cell<'_> {
+    /// Checks whether the column `x` belongs to this cell
+    const fn contains(&self, x: usize) -> bool {
+        x >= self.x && x - self.x < self.width
+    }
+
+    /// Returns the kind of the column in this cell, or `None` for a separator
+    const fn kind(&self) -> Option<&ColumnKind> {
+        match self.part {
+            Part::Column(column) => Some(&column.kind),
+            Part::Separator(_) => None,
+        }
+    }
+}
+
+/// calcs the position in a line with spacing
+fn get_cursor_offset(vec: Vec2, bytes_per_group: usize, separator: &str) -> Vec2 {
+    (vec.x / (2 * bytes_per_group) * separator.width(), 0).into()
+}
+
+/// returns the number of hex digits needed to display `value`, but at least 1
+const fn get_hex_digit_count(value: u64) -> usize {
+    let bits = u64::BITS - value.leading_zeros();
+    if bits == 0 {
+        1
+    } else {
+        bits.div_ceil(4) as usize
+    }
+}
+
+/// returns the rows which are (at least partially) visible in `printer`
+fn get_visible_rows(printer: &Printer, height: usize) -> Range<usize> {
+    let first = min(printer.content_offset.y, height);
+    first..min(height, first + printer.output_size.y)
+}
+
+/// returns the width of the address column and its separator, if the layout starts with them
+///
+/// They stay in place when the view scrolls horizontally.
+fn get_pinned_width(layout: &[Cell]) -> usize {
+    match layout {
+        [addr, sep, ..] if matches!(addr.kind(), Some(ColumnKind::Addr)) && sep.kind().is_none() => sep.x + sep.width,
+        [addr, ..] if matches!(addr.kind(), Some(ColumnKind::Addr)) => addr.width,
+        _ => 0,
+    }
+}
+
+/// the longest time between two clicks on the same byte which counts as double click
+const DOUBLE_CLICK_TIME: Duration = Duration::from_millis(500);
+
+/// the number of rows scrolled by one step of the mouse wheel
+const WHEEL_ROWS: usize = 3;
+
+/// returns the scroll position of an axis where `visible` of `content` cells are shown, so that `pos` is visible
+fn get_scroll_position(scroll: usize, pos: Option<usize>, visible: usize, content: usize) -> usize {
+    let scroll = min(scroll, content.saturating_sub(visible));
+    match pos {
+        Some(pos) if pos < scroll => pos,
+        Some(pos) if pos >= scroll + visible => pos + 1 - std::cmp::max(visible, 1),
+        _ => scroll,
+    }
+}
+
+/// the character which represents an unknown byte in the text column
+const UNKNOWN_CHAR: char = '░';
+
+/// converts the character either to itself if it `is_ascii_graphic`
+fn make_printable<T: Borrow<u8>>(c: T) -> char {
+    let c = *c.borrow();
+    if c.is_ascii_graphic() {
+        c as char
+    } else {
+        '.'
+    }
+}
+
+// implements helper functions for this struct
+impl HexView {
+    /// Returns the address of the byte at `offset` in the address space.
+    ///
+    /// Addresses wrap around at `u64::MAX`, like in a real 64 bit address space.
+    fn get_addr(&self, offset: u64) -> u64 {
+        self.config.start_addr.wrapping_add(offset)
+    }
+
+    /// Counts how many digits we need to align the addresses evenly.
+    ///
+    /// E.g. we need 2 digits for 20 elements (0x13 is the last address), but only 1 for 10 elements (0x9)
+    fn get_addr_digit_length(&self) -> usize {
+        let last = self.regions.last().map_or(0, |r| r.end().saturating_sub(1));
+        std::cmp::max(
+            get_hex_digit_count(self.config.start_addr.saturating_add(last)),
+            self.config.bytes_per_addr,
+        )
+    }
+
+    /// Returns the number of bytes per line, which is at least 1
+    fn bytes_per_line(&self) -> usize {
+        match (self.config.fit_width, self.fitted_bytes_per_line) {
+            (FitWidth::Off, _) | (_, None) => std::cmp::max(1, self.config.bytes_per_line),
+            (_, Some(fitted)) => fitted,
+        }
+    }
+
+    /// Returns the number of bytes per group, which is at least 1 and at most the number of bytes per line
+    fn bytes_per_group(&self) -> usize {
+        self.get_bytes_per_group(self.bytes_per_line())
+    }
+
+    /// Returns the number of bytes per group for lines of `bytes_per_line` bytes
+    fn get_bytes_per_group(&self, bytes_per_line: usize) -> usize {
+        self.config.bytes_per_group.clamp(1, bytes_per_line)
+    }
+
+    /// Returns the largest number of bytes per line whose layout fits into `width`
+    ///
+    /// Returns `None` if fitting is disabled.
+    fn fit_bytes_per_line(&self, width: usize) -> Option<usize> {
+        let candidate = |n: usize| match self.config.fit_width {
+            FitWidth::Off => None,
+            FitWidth::Groups => n.checked_mul(std::cmp::max(1, self.config.bytes_per_group)),
+            FitWidth::PowersOfTwo => 1usize.checked_shl((n - 1) as u32),
+        };
+
+        let mut best = candidate(1)?;
+        // every byte takes at least one cell, so there is no need to look at longer lines than `width`
+        for bytes_per_line in (2..).map_while(candidate).take_while(|n| *n <= std::cmp::max(1, width)) {
+            if self.get_layout_width(bytes_per_line) > width {
+                break;
+            }
+            best = bytes_per_line;
+        }
+        Some(best)
+    }
+
+    /// Switches to `bytes_per_line` bytes per line, the cursor stays on the same nibble
+    fn reflow(&mut self, bytes_per_line: Option<usize>) {
+        self.fitted_bytes_per_line = bytes_per_line;
+        self.clamp_cursor();
+    }
+
+    /// Maps the displayed rows to the lines of the regions
+    fn get_row_map(&self) -> RowMap {
+        RowMap::new(&self.regions, self.bytes_per_line())
+    }
+
+    /// Counts how many rows we need to display the complete data
+    fn get_widget_height(&self) -> usize {
+        self.get_row_map().len()
+    }
+
+    /// Returns the line which is displayed in `row`, or `None` if the row is a separator or out of range
+    fn get_line_of_row(&self, row: usize) -> Option<u64> {
+        match self.get_row_map().row(row) {
+            Some(Row::Line(line)) => Some(line),
+            _ => None,
+        }
+    }
+
+    /// Returns the byte at `offset`, or `None` if it is not mapped by any region or unknown
+    fn get_byte(&self, offset: u64) -> Option<u8> {
+        let idx = self.regions.partition_point(|r| r.offset() <= offset).checked_sub(1)?;
+        let region = &self.regions[idx];
+        if !region.contains(offset) {
+            return None;
+        }
+        region.get((offset - region.offset()) as usize)
+    }
+
+    /// Returns the character for the byte at `offset` in the text column
+    fn get_char(&self, offset: u64, byte: Option<u8>) -> char {
+        match byte {
+            Some(byte) => make_printable(byte),
+            None if self.is_mapped(offset) => UNKNOWN_CHAR,
+            None => ' ',
+        }
+    }
+
+    /// Returns the bytes of `line` up to the last mapped one, unmapped bytes are `None`
+    fn get_line(&self, line: u64) -> Vec<Option<u8>> {
+        self.get_bytes(line * self.bytes_per_line() as u64, self.bytes_per_line())
+    }
+
+    /// Returns up to `len` bytes starting at `first`, up to the last mapped one. Unmapped and unknown bytes are
+    /// `None`
+    fn get_bytes(&self, first: u64, len: usize) -> Vec<Option<u8>> {
+        let mut bytes: Vec<_> = (0..len as u64)
+            .map(|i| first.checked_add(i).and_then(|offset| self.get_byte(offset)))
+            .collect();
+        while let Some(last) = (bytes.len() as u64).checked_sub(1) {
+            let mapped = first.checked_add(last).is_some_and(|offset| self.is_mapped(offset));
+            if bytes[bytes.len() - 1].is_some() || mapped {
+                break;
+            }
+            bytes.pop();
+        }
+        bytes
+    }
+
+    /// Checks whether the byte at `offset` belongs to a region
+    fn is_mapped(&self, offset: u64) -> bool {
+        let idx = self.regions.partition_point(|r| r.offset() <= offset);
+        idx.checked_sub(1).is_some_and(|idx| self.regions[idx].contains(offset))
+    }
+
+    /// Returns the number of bytes in `line`, including unmapped ones in front of the last mapped byte
+    fn get_line_len(&self, line: u64) -> usize {
+        self.get_line(line).len()
+    }
+
+    /// Returns the line of the byte at `offset`
+    fn get_line_of_offset(&self, offset: u64) -> u64 {
+        offset / self.bytes_per_line() as u64
+    }
+
+    /// Returns the position of the byte at `offset` inside its line
+    fn get_column_of_offset(&self, offset: u64) -> usize {
+        (offset % self.bytes_per_line() as u64) as usize
+    }
+
+    /// Returns the row which displays the cursor
+    fn get_cursor_row(&self) -> usize {
+        self.get_row_map()
+            .nearest_row(self.get_line_of_offset(self.cursor.offset))
+    }
+
+    /// Returns the position of the cursor in the grid of nibbles, without any spacing
+    ///
+    /// e.g. the low nibble of the third byte in the second row is at (5, 1)
+    fn get_cursor_pos(&self) -> Vec2 {
+        let column = self.get_column_of_offset(self.cursor.offset);
+        (column * 2 + self.cursor.digit, self.get_cursor_row()).into()
+    }
+
+    /// calcs the offset to the current position to match the spacing we insert to group the hex chars.
+    ///
+    /// e.g. cursor (5, 0) will result in (6, 0) because of the 1 space spacing after the fourth char
+    /// and cursor (9, 0) will result in (11, 0) because of the 1+1 spacing after the fourth and eighth char
+    fn get_cursor_offset(&self) -> Vec2 {
+        let pos = self.get_cursor_pos();
+        pos + get_cursor_offset(pos, self.bytes_per_group(), &self.config.byte_group_separator)
+    }
+
+    /// gets the amount of bytes in `row`, including unmapped ones in front of the last mapped byte
+    fn get_elements_in_row(&self, row: usize) -> usize {
+        self.get_line_of_row(row).map_or(0, |line| self.get_line_len(line))
+    }
+
+    /// gets the max cursor-x position in `row`
+    fn get_max_x_in_row(&self, row: usize) -> usize {
+        (self.get_elements_in_row(row) * 2).saturating_sub(1)
+    }
+
+    /// Returns the row of the nearest line above (`up`) or below the cursor, skipping separators.
+    fn get_next_line_row(&self, up: bool) -> Option<usize> {
+        let rows = self.get_row_map();
+        let mut row = self.get_cursor_row();
+        loop {
+            row = if up { row.checked_sub(1)? } else { row + 1 };
+            if let Row::Line(_) = rows.row(row)? {
+                return Some(row);
+            }
+        }
+    }
+
+    /// Moves the cursor to the nibble at `pos` in the grid of nibbles, see [`get_cursor_pos`].
+    ///
+    /// Positions on a separator are moved to the next line, positions behind the end of a line to its last nibble.
+    fn set_cursor_pos(&mut self, pos: Vec2) {
+        let rows = self.get_row_map();
+        let row = min(pos.y, rows.len() - 1);
+        // the last row is always a line, so we can always move down from a separator
+        let row = if let Some(Row::Gap(_)) = rows.row(row) {
+            row + 1
+        } else {
+            row
+        };
+        let line = self.get_line_of_row(row).unwrap_or(0);
+        let x = min(pos.x, self.get_max_x_in_row(row));
+
+        self.cursor = Cursor {
+            offset: line * self.bytes_per_line() as u64 + (x / 2) as u64,
+            digit: x % 2,
+        };
+        self.follow_cursor = true;
+    }
+
+    /// Moves the cursor back into the data, e.g. after the data has been truncated
+    ///
+    /// If the line of the cursor is no longer displayed, the cursor moves to the start of the next displayed line.
+    fn clamp_cursor(&mut self) {
+        let line = self.get_line_of_offset(self.cursor.offset);
+        let row = self.get_row_map().nearest_row(line);
+        let pos = match self.get_line_of_row(row) {
+            Some(l) if l == line => self.get_cursor_pos(),
+            Some(l) if l > line => (0, row).into(),
+            _ => (usize::MAX, row).into(),
+        };
+        self.set_cursor_pos(pos);
+    }
+
+    /// advances the cursor by one nibble
+    ///
+    /// Returns either an `EventResult::Ignored` if the end of
+    /// the line is reached or `EventResult::Consumed(None)` if it was successful.
+    fn cursor_x_advance(&mut self) -> EventResult {
+        let pos = self.get_cursor_pos();
+        if pos.x == self.get_max_x_in_row(pos.y) {
+            return EventResult::Ignored;
+        }
+
+        self.set_cursor_pos(pos.map_x(|x| x + 1));
+        EventResult::Consumed(None)
+    }
+
+    /// Moves the cursor back by one nibble
+    ///
+    /// Returns `EventResult::Ignored` if the cursor is already at the start of the line.
+    fn cursor_x_retreat(&mut self) -> EventResult {
+        let pos = self.get_cursor_pos();
+        if pos.x == 0 {
+            return EventResult::Ignored;
+        }
+
+        self.set_cursor_pos(pos.map_x(|x| x - 1));
+        EventResult::Consumed(None)
+    }
+
+    /// Moves the cursor, selects or scrolls according to the mouse `event` at `position`
+    fn handle_mouse(&mut self, position: Vec2, event: MouseEvent) -> EventResult {
+        match event {
+            MouseEvent::Press(button) => {
+                let pos = self.convert_visual_to_real_cursor(position);
+                self.set_cursor_pos(pos);
+                if button != MouseButton::Left {
+                    return EventResult::Consumed(None);
+                }
+
+                let offset = self.cursor.offset;
+                let now = Instant::now();
+                let double_click = self
+                    .last_click
+                    .is_some_and(|(time, last)| last == offset && now.duration_since(time) <= DOUBLE_CLICK_TIME);
+                if double_click {
+                    self.select_group();
+                    self.last_click = None;
+                    self.drag_start = None;
+                } else {
+                    self.selection = None;
+                    self.last_click = Some((now, offset));
+                    self.drag_start = Some(offset);
+                }
+            }
+            MouseEvent::Hold(MouseButton::Left) => {
+                let Some(start) = self.drag_start else {
+                    return EventResult::Ignored;
+                };
+                let pos = self.convert_visual_to_real_cursor(position);
+                self.set_cursor_pos(pos);
+                let offset = self.cursor.offset;
+                self.selection = (offset != start).then(|| (min(start, offset), std::cmp::max(start, offset)));
+            }
+            MouseEvent::Release(_) => self.drag_start = None,
+            MouseEvent::WheelUp | MouseEvent::WheelDown => {
+                let old = self.v_scroll;
+                let max = self.get_widget_height().saturating_sub(self.get_page_height());
+                self.v_scroll = if event == MouseEvent::WheelUp {
+                    old.saturating_sub(WHEEL_ROWS)
+                } else {
+                    min(old.saturating_add(WHEEL_ROWS), max)
+                };
+                if self.v_scroll == old {
+                    // let a surrounding scroll view handle it
+                    return EventResult::Ignored;
+                }
+                self.follow_cursor = false;
+            }
+            _ => return EventResult::Ignored,
+        }
+        EventResult::Consumed(None)
+    }
+
+    /// Selects the byte group under the cursor, up to the end of the line
+    fn select_group(&mut self) {
+        let bytes_per_group = self.bytes_per_group() as u64;
+        let line = self.get_line_of_offset(self.cursor.offset);
+        let line_start = line * self.bytes_per_line() as u64;
+        let line_end = line_start.saturating_add(self.get_line_len(line) as u64);
+        let column = self.get_column_of_offset(self.cursor.offset) as u64;
+        let first = line_start + column / bytes_per_group * bytes_per_group;
+        let last = min(first.saturating_add(bytes_per_group), line_end).saturating_sub(1);
+        self.selection = Some((first, std::cmp::max(first, last)));
+    }
+
+    /// Moves the cursor by the number of visible rows up or down
+    ///
+    /// Returns `EventResult::Ignored` if the cursor is already in the first or last line.
+    fn cursor_page_move(&mut self, up: bool) -> EventResult {
+        if self.get_next_line_row(up).is_none() {
+            return EventResult::Ignored;
+        }
+
+        let page = self.get_page_height();
+        let rows = self.get_row_map();
+        let row = self.get_cursor_row();
+        let target = if up {
+            row.saturating_sub(page)
+        } else {
+            min(row.saturating_add(page), rows.len() - 1)
+        };
+        // the first row is always a line, separators are skipped downwards by `set_cursor_pos`
+        let target = match rows.row(target) {
+            Some(Row::Gap(_)) if up => target - 1,
+            _ => target,
+        };
+
+        self.v_scroll = if up {
+            self.v_scroll.saturating_sub(page)
+        } else {
+            self.v_scroll.saturating_add(page)
+        };
+        self.cursor_y_move(target);
+        EventResult::Consumed(None)
+    }
+
+    /// Moves the cursor to the start of the next (`forward`) or current byte group
+    ///
+    /// If the cursor is already at the start of a group, it moves to the start of the previous one. Groups in
+    /// the next or previous line are reached as well. Returns `EventResult::Ignored` if there is no such group.
+    fn cursor_group_move(&mut self, forward: bool) -> EventResult {
+        let bytes_per_group = self.bytes_per_group() as u64;
+        let line = self.get_line_of_offset(self.cursor.offset);
+        let line_start = line * self.bytes_per_line() as u64;
+        let column = self.get_column_of_offset(self.cursor.offset) as u64;
+        let group_start = line_start + column / bytes_per_group * bytes_per_group;
+
+        let target = if forward {
+            let next = group_start.saturating_add(bytes_per_group);
+            if next < line_start.saturating_add(self.get_line_len(line) as u64) {
+                Some(next)
+            } else {
+                self.get_next_line_row(false)
+                    .and_then(|row| self.get_line_of_row(row))
+                    .map(|line| line * self.bytes_per_line() as u64)
+            }
+        } else if self.cursor.offset != group_start || self.cursor.digit != 0 {
+            Some(group_start)
+        } else if group_start != line_start {
+            Some(group_start - bytes_per_group)
+        } else {
+            self.get_next_line_row(true)
+                .and_then(|row| self.get_line_of_row(row))
+                .map(|line| {
+                    let last = self.get_line_len(line).saturating_sub(1) as u64;
+                    line * self.bytes_per_line() as u64 + last / bytes_per_group * bytes_per_group
                 })
-                .format(self.config.byte_group_separator);
-            printer.print((0, i), &format!("{hex}"));
+        };
+
+        match target {
+            Some(offset) => {
+                self.move_cursor_to(offset);
+                EventResult::Consumed(None)
+            }
+            None => EventResult::Ignored,
+        }
+    }
+
+    /// Moves the cursor to the first hex digit of the byte at `offset`
+    fn move_cursor_to(&mut self, offset: u64) {
+        self.cursor = Cursor { offset, digit: 0 };
+        self.clamp_cursor();
+    }
+
+    /// Returns the offset of the nearest known byte after (`forward`) or before the cursor, which matches `pred`
+    fn find_byte<P: Fn(u8) -> bool>(&self, forward: bool, pred: P) -> Option<u64> {
+        let from = self.cursor.offset;
+        let matches = |r: &Region, pos: usize| r.get(pos).is_some_and(&pred);
+        if forward {
+            let start = from.checked_add(1)?;
+            self.regions.iter().find_map(|r| {
+                let skip = min(start.saturating_sub(r.offset()), r.len() as u64) as usize;
+                (skip..r.len())
+                    .find(|&pos| matches(r, pos))
+                    .map(|pos| r.offset() + pos as u64)
+            })
+        } else {
+            self.regions.iter().rev().find_map(|r| {
+                let take = min(from.saturating_sub(r.offset()), r.len() as u64) as usize;
+                (0..take)
+                    .rev()
+                    .find(|&pos| matches(r, pos))
+                    .map(|pos| r.offset() + pos as u64)
+            })
+        }
+    }
+
+    /// Returns the offset of the first match of `pattern` starting at or after `from` (`forward`), or of the last
+    /// match starting before `from`
+    fn find_pattern(&self, pattern: &[u8], forward: bool, from: u64) -> Option<u64> {
+        if pattern.is_empty() {
+            return None;
+        }
+        let matches = |r: &Region, pos: usize| (0..pattern.len()).all(|i| r.get(pos + i) == Some(pattern[i]));
+        if forward {
+            self.regions.iter().find_map(|r| {
+                let skip = min(from.saturating_sub(r.offset()), r.len() as u64) as usize;
+                let last = r.len().checked_sub(pattern.len())?;
+                (skip..=last)
+                    .find(|&pos| matches(r, pos))
+                    .map(|pos| r.offset() + pos as u64)
+            })
+        } else {
+            self.regions.iter().rev().find_map(|r| {
+                let take = min(from.saturating_sub(r.offset()), r.len() as u64) as usize;
+                let last = min(take.checked_sub(1)?, r.len().checked_sub(pattern.len())?);
+                (0..=last)
+                    .rev()
+                    .find(|&pos| matches(r, pos))
+                    .map(|pos| r.offset() + pos as u64)
+            })
+        }
+    }
+
+    /// Moves the cursor to a match of the search pattern at `found` and selects it
+    fn select_match(&mut self, found: Option<u64>) -> bool {
+        let (Some(offset), Some(pattern)) = (found, self.search_pattern.as_ref()) else {
+            return false;
+        };
+        self.selection = Some((offset, offset + (pattern.len() - 1) as u64));
+        if offset != self.cursor.offset {
+            self.record_jump();
         }
+        self.move_cursor_to(offset);
+        self.scroll_to_cursor();
+        true
     }
 
-    /// draws the ascii seperator between the hex and ascii representation
-    fn draw_ascii_sep(&self, printer: &Printer) {
-        printer.print_vline((0, 0), self.get_widget_height(), self.config.hex_ascii_separator);
+    /// Updates the status shown by the status views
+    fn publish_status(&self) {
+        *self.status.lock().unwrap_or_else(PoisonError::into_inner) = self.status();
+    }
+
+    /// Returns a callback which shows the [`help`](#method.help) as a table in a dialog
+    fn show_help(&self) -> impl Fn(&mut Cursive) + Send + Sync + 'static {
+        let help = self.help();
+        let width = help.iter().map(|(keys, _)| keys.width()).max().unwrap_or(0);
+        let text = help
+            .iter()
+            .map(|(keys, description)| format!("{keys}{}  {description}", " ".repeat(width - keys.width())))
+            .join("\n");
+        move |s| {
+            s.add_layer(
+                Dialog::around(TextView::new(text.clone()).scrollable())
+                    .title("Keys")
+                    .dismiss_button("Close"),
+            );
+        }
+    }
+
+    /// Applies `edit` and records it for undo
+    ///
+    /// Returns `false` if the edit is not possible, see [`Edit::can_apply`], or cannot be written back to the
+    /// data source.
+    fn apply_edit(&mut self, edit: Edit) -> bool {
+        if !edit.can_apply(&self.regions) || !self.write_back(edit) {
+            return false;
+        }
+        edit.apply(&mut self.regions);
+        if edit.is_resize() {
+            self.layout_changed = true;
+        }
+        self.history.push(edit);
+        true
+    }
+
+    /// Applies an edit taken from the history and moves the cursor to it, the history is not changed
+    fn replay(&mut self, edit: Option<Edit>) -> bool {
+        let Some(edit) = edit.filter(|&e| e.can_apply(&self.regions) && self.write_back(e)) else {
+            return false;
+        };
+        edit.apply(&mut self.regions);
+        if edit.is_resize() {
+            self.layout_changed = true;
+        }
+        self.move_cursor_to(edit.offset());
+        self.scroll_to_cursor();
+        true
+    }
+
+    /// Sets the nibble under the cursor to `value`, moving to the next nibble if `advance` is set
+    ///
+    /// In insert mode, advancing from a high nibble inserts a new byte instead.
+    fn set_nibble(&mut self, value: u8, advance: bool) -> EventResult {
+        let offset = self.cursor.offset;
+        let high = self.cursor.digit == 0;
+        if self.insert_mode && advance && high {
+            if self.apply_edit(Edit::Insert {
+                offset,
+                byte: value << 4,
+            }) {
+                self.cursor.digit = 1;
+            }
+        } else if let Some(old) = self.get_byte(offset) {
+            let shift = if high { 4 } else { 0 };
+            let new = (old & !(0xF << shift)) | ((value & 0xF) << shift);
+            self.apply_edit(Edit::Set { offset, old, new });
+            if advance {
+                self.cursor_x_advance();
+            }
+        }
+        EventResult::Consumed(None)
+    }
+
+    /// Removes the byte at `offset`, returning `EventResult::Ignored` if it is not mapped
+    fn remove_byte(&mut self, offset: Option<u64>) -> EventResult {
+        match offset.and_then(|offset| Some((offset, self.get_byte(offset)?))) {
+            Some((offset, byte)) => {
+                self.apply_edit(Edit::Remove { offset, byte });
+                self.clamp_cursor();
+                EventResult::Consumed(None)
+            }
+            None => EventResult::Ignored,
+        }
+    }
+
+    /// Moves the cursor to `row`, keeping the column if possible
+    fn cursor_y_move(&mut self, row: usize) {
+        let x = self.get_cursor_pos().x;
+        self.set_cursor_pos((x, row).into());
+    }
+
+    /// Gets the element under the cursor
+    ///
+    /// (which points to a nibble, but we are interested in the
+    /// whole u8)
+    ///
+    /// Returns none if the cursor is on an unmapped byte.
+    fn get_element_under_cursor(&self) -> Option<u8> {
+        self.get_byte(self.cursor.offset)
+    }
+
+    /// Converts the visual position to a position in the grid of nibbles.
+    ///
+    /// This function is used to convert the
+    /// point where the mouse clicked to the real cursor position without padding
+    ///
+    /// Clicks into a hex column select the nibble under the mouse, clicks into a text column the byte under the
+    /// mouse and clicks anywhere else only select the row.
+    fn convert_visual_to_real_cursor(&self, pos: Vec2) -> Vec2 {
+        let x = if pos.x < get_pinned_width(&self.get_layout()) {
+            pos.x
+        } else {
+            pos.x + self.h_scroll
+        };
+
+        let nibble = match self.get_cell_at(x) {
+            Some(cell) if matches!(cell.kind(), Some(ColumnKind::Hex)) => {
+                let group_digits = 2 * self.bytes_per_group();
+                let group_width = group_digits + self.config.byte_group_separator.width();
+                let x = x - cell.x;
+                x / group_width * group_digits + min(x % group_width, group_digits - 1)
+            }
+            Some(cell) if matches!(cell.kind(), Some(ColumnKind::Text)) => (x - cell.x) * 2,
+            _ => self.get_cursor_pos().x,
+        };
+
+        (nibble, pos.y + self.v_scroll).into()
+    }
+
+    /// returns the displayed characters of a column of `kind` for lines of `bytes_per_line` bytes
+    fn get_column_width(&self, kind: &ColumnKind, bytes_per_line: usize) -> usize {
+        match kind {
+            ColumnKind::Addr => self.get_gutter_width() + self.get_addr_digit_length(),
+            ColumnKind::Hex => {
+                let groups = bytes_per_line.div_ceil(self.get_bytes_per_group(bytes_per_line));
+                2 * bytes_per_line + (groups - 1) * self.config.byte_group_separator.width()
+            }
+            ColumnKind::Text => bytes_per_line,
+            ColumnKind::Custom(renderer) => renderer.width(bytes_per_line),
+        }
+    }
+
+    /// Checks whether `column` is displayed
+    fn is_column_shown(&self, column: &Column) -> bool {
+        column.enabled && (self.config.show_ascii || !matches!(column.kind, ColumnKind::Text))
+    }
+
+    /// Places the displayed columns and the separators between them next to each other
+    fn get_layout(&self) -> Vec<Cell<'_>> {
+        self.get_layout_for(self.bytes_per_line())
+    }
+
+    /// Returns the width of all columns and separators for lines of `bytes_per_line` bytes
+    fn get_layout_width(&self, bytes_per_line: usize) -> usize {
+        self.get_layout_for(bytes_per_line)
+            .last()
+            .map_or(0, |cell| cell.x + cell.width)
+    }
+
+    /// Places the displayed columns for lines of `bytes_per_line` bytes
+    fn get_layout_for(&self, bytes_per_line: usize) -> Vec<Cell<'_>> {
+        let mut cells = Vec::new();
+        let mut x = 0;
+        let mut prev: Option<&Column> = None;
+        for column in self.config.columns.iter().filter(|c| self.is_column_shown(c)) {
+            if let Some(prev) = prev {
+                let separator = match (&column.separator, &prev.kind) {
+                    (Some(separator), _) => separator,
+                    (None, ColumnKind::Addr) => &self.config.addr_hex_separator,
+                    (None, _) => &self.config.hex_ascii_separator,
+                };
+                let width = separator.width();
+                cells.push(Cell {
+                    x,
+                    width,
+                    part: Part::Separator(separator),
+                });
+                x += width;
+            }
+
+            let width = self.get_column_width(&column.kind, bytes_per_line);
+            cells.push(Cell {
+                x,
+                width,
+                part: Part::Column(column),
+            });
+            x += width;
+            prev = Some(column);
+        }
+        cells
+    }
+
+    /// Returns the cell which is displayed at column `x`
+    fn get_cell_at(&self, x: usize) -> Option<Cell<'_>> {
+        self.get_layout().into_iter().find(|cell| cell.contains(x))
+    }
+
+    /// Scrolls, so that the cursor is visible and no space is wasted
+    ///
+    /// After scrolling with the mouse wheel, the cursor is not followed until it moves again.
+    fn scroll_to_cursor(&mut self) {
+        let Some(size) = self.size else {
+            return;
+        };
+
+        let layout = self.get_layout();
+        let pinned = get_pinned_width(&layout);
+        let content = layout.last().map_or(0, |cell| cell.x + cell.width);
+        let cursor = layout
+            .iter()
+            .find(|cell| matches!(cell.kind(), Some(ColumnKind::Hex)))
+            .map(|cell| cell.x + self.get_cursor_offset().x)
+            .or_else(|| {
+                layout
+                    .iter()
+                    .find(|cell| matches!(cell.kind(), Some(ColumnKind::Text)))
+                    .map(|cell| cell.x + self.get_cursor_pos().x / 2)
+            });
+
+        let follow = self.follow_cursor;
+        self.h_scroll = get_scroll_position(
+            self.h_scroll,
+            cursor.and_then(|pos| pos.checked_sub(pinned)).filter(|_| follow),
+            size.x.saturating_sub(pinned),
+            content.saturating_sub(pinned),
+        );
+        self.v_scroll = get_scroll_position(
+            self.v_scroll,
+            Some(self.get_cursor_row()).filter(|_| follow),
+            size.y,
+            self.get_widget_height(),
+        );
+    }
+
+    /// Returns the number of rows which are visible at once
+    fn get_page_height(&self) -> usize {
+        let height = self.size.map_or(usize::MAX, |size| size.y);
+        height.clamp(1, self.get_widget_height())
+    }
+}
+
+// implements draw-helper functions
+// with the default columns it will look as follows
+// addr: hexehex hexhex hexhex ... | asciiiiiii
+// the addr field will be padded, so that all addresses are equal in length
+// the hex field will be grouped by 4 character (nibble) and seperated by 1 space
+// the seperator is a special pipe, which is longer and connects with the lower and bottom "pipe" (BOX DRAWINGS LIGHT VERTICAL \u{2502})
+// custom columns are drawn line by line by their renderer
+// the ascii part is just the ascii char of the coressponding hex value if it is [graphical](https://doc.rust-lang.org/std/primitive.u8.html#method.is_ascii_graphic), if not it will be displayed as a dot (.)
+// unmapped bytes are displayed as `??` in the hex and as space in the ascii field, unknown bytes as `??` and `░`,
+// unmapped lines between two regions are replaced by a single horizontal line, which carries the name of the following region
+// bookmarked lines carry the name of their bookmark in front of the address
+impl HexView {
+    /// draws the addr field into the printer
+    fn draw_addr(&self, printer: &Printer) {
+        let digits_len = self.get_addr_digit_length();
+        let gutter = self.get_gutter_width();
+        let rows = self.get_row_map();
+        for row in get_visible_rows(printer, rows.len()) {
+            if let Some(Row::Line(line)) = rows.row(row) {
+                if let Some(name) = self.get_bookmark_in_line(line) {
+                    printer.with_color(ColorStyle::title_secondary(), |p| p.print((0, row), &name.to_string()));
+                }
+                printer.print(
+                    (gutter, row),
+                    &format!(
+                        "{:0len$X}",
+                        self.get_addr(line * self.bytes_per_line() as u64),
+                        len = digits_len
+                    ),
+                );
+            }
+        }
+    }
+
+    /// draws a separator between two columns
+    fn draw_separator(&self, printer: &Printer, separator: &str) {
+        printer.print_vline((0, 0), self.get_widget_height(), separator);
+    }
+
+    /// draws the hex fields between the addr and ascii representation
+    fn draw_hex(&self, printer: &Printer) {
+        let rows = self.get_row_map();
+        for row in get_visible_rows(printer, rows.len()) {
+            match rows.row(row) {
+                Some(Row::Line(line)) => {
+                    let bytes = self.get_line(line);
+                    let hex = bytes
+                        .chunks(self.bytes_per_group())
+                        .map(|c| {
+                            let mut s = String::new();
+                            for b in c {
+                                match b {
+                                    Some(b) => write!(&mut s, "{:02X}", b).expect("Unable to write hex values"),
+                                    None => s.push_str("??"),
+                                }
+                            }
+                            s
+                        })
+                        .format(&self.config.byte_group_separator);
+                    printer.print((0, row), &format!("{hex}"));
+                }
+                Some(Row::Gap(next)) => {
+                    printer.print_hline((0, row), printer.size.x, "─");
+                    let name = self.regions[next].name();
+                    if !name.is_empty() {
+                        printer.print((1, row), &format!(" {name} "));
+                    }
+                }
+                None => {}
+            }
+        }
     }
 
     /// draws the ascii chars
     fn draw_ascii(&self, printer: &Printer) {
-        for (i, c) in self.data.chunks(self.config.bytes_per_line).enumerate() {
-            let ascii: String = c.iter().map(make_printable).collect();
-            printer.print((0, i), &ascii);
+        let rows = self.get_row_map();
+        for row in get_visible_rows(printer, rows.len()) {
+            if let Some(Row::Line(line)) = rows.row(row) {
+                let first = line * self.bytes_per_line() as u64;
+                let ascii: String = (first..)
+                    .zip(self.get_line(line))
+                    .map(|(offset, b)| self.get_char(offset, b))
+                    .collect();
+                printer.print((0, row), &ascii);
+            }
+        }
+    }
+
+    /// draws a user defined column
+    fn draw_custom(&self, printer: &Printer, renderer: &dyn ColumnRenderer) {
+        let rows = self.get_row_map();
+        let bytes_per_line = self.bytes_per_line() as u64;
+        for row in get_visible_rows(printer, rows.len()) {
+            if let Some(Row::Line(line)) = rows.row(row) {
+                let first = line * bytes_per_line;
+                let bytes: Vec<_> = (0..bytes_per_line)
+                    .map(|i| first.checked_add(i).and_then(|offset| self.get_byte(offset)))
+                    .collect();
+                printer.print((0, row), &renderer.render(self.get_addr(first), &bytes));
+            }
+        }
+    }
+
+    /// draws the content of a column of `kind`
+    fn draw_column(&self, printer: &Printer, kind: &ColumnKind) {
+        match kind {
+            ColumnKind::Addr => self.draw_addr(printer),
+            ColumnKind::Hex => self.draw_hex(printer),
+            ColumnKind::Text => self.draw_ascii(printer),
+            ColumnKind::Custom(renderer) => self.draw_custom(printer, &**renderer),
         }
     }
 
     /// this highlights the complete hex byte under the cursor
     fn highlight_current_hex(&self, printer: &Printer) {
-        if let Some(elem) = self.get_element_under_cursor() {
-            let high = self.cursor.x % 2 == 0;
+        if self.get_elements_in_row(self.get_cursor_row()) > 0 {
+            let high = self.cursor.digit == 0;
             let hpos = self.get_cursor_offset();
             let dpos = hpos.map_x(|x| if high { x + 1 } else { x - 1 });
 
-            let fem = format!("{elem:02X}");
+            let fem = self
+                .get_element_under_cursor()
+                .map_or_else(|| "??".to_owned(), |elem| format!("{elem:02X}"));
             let s = fem.split_at(1);
             let ext = |hl| if hl { s.0 } else { s.1 };
 
@@ -592,148 +2203,356 @@ impl HexView {
         }
     }
 
+    /// this highlights the selected bytes in a hex (`hex`) or text column
+    fn highlight_selection(&self, printer: &Printer, hex: bool) {
+        if let Some((first, last)) = self.selection {
+            self.highlight_bytes(printer, hex, ColorStyle::highlight_inactive(), |offset| {
+                (first..=last).contains(&offset)
+            });
+        }
+    }
+
+    /// this highlights the bytes which changed during the last polls of the data source
+    fn highlight_changes(&self, printer: &Printer, hex: bool) {
+        if !self.changes.is_empty() {
+            printer.with_effect(Effect::Bold, |printer| {
+                self.highlight_bytes(printer, hex, ColorStyle::title_primary(), |offset| {
+                    self.is_flashing(offset)
+                });
+            });
+        }
+    }
+
+    /// this redraws the visible bytes for which `highlight` returns true with `style`
+    fn highlight_bytes<F: Fn(u64) -> bool>(&self, printer: &Printer, hex: bool, style: ColorStyle, highlight: F) {
+        let bytes_per_line = self.bytes_per_line() as u64;
+        let rows = self.get_row_map();
+        printer.with_color(style, |printer| {
+            for row in get_visible_rows(printer, rows.len()) {
+                let Some(Row::Line(line)) = rows.row(row) else {
+                    continue;
+                };
+                let line_start = line * bytes_per_line;
+                for (column, byte) in self.get_line(line).into_iter().enumerate() {
+                    let offset = line_start + column as u64;
+                    if !highlight(offset) {
+                        continue;
+                    }
+                    if hex {
+                        let pos = Vec2::new(column * 2, row);
+                        let pos =
+                            pos + get_cursor_offset(pos, self.bytes_per_group(), &self.config.byte_group_separator);
+                        let text = byte.map_or_else(|| "??".to_owned(), |b| format!("{b:02X}"));
+                        printer.print(pos, &text);
+                    } else {
+                        printer.print((column, row), &self.get_char(offset, byte).to_string());
+                    }
+                }
+            }
+        });
+    }
+
     /// this highlights the corresponding ascii value of the hex which is under the cursor
     fn highlight_current_ascii(&self, printer: &Printer) {
-        if let Some(elem) = self.get_element_under_cursor() {
-            let pos = self.cursor.map_x(|x| x / 2);
-            let ascii = make_printable(elem);
+        if self.get_elements_in_row(self.get_cursor_row()) > 0 {
+            let pos = self.get_cursor_pos().map_x(|x| x / 2);
+            let ascii = self.get_char(self.cursor.offset, self.get_element_under_cursor());
             printer.with_color(ColorStyle::highlight(), |p| p.print(pos, &ascii.to_string()));
         }
     }
 }
 
-impl View for HexView {
-    fn on_event(&mut self, event: Event) -> EventResult {
+// implements the event handling
+impl HexView {
+    /// Moves the cursor or edits the data according to `event`
+    fn handle_event(&mut self, event: Event) -> EventResult {
         if self.state == DisplayState::Disabled {
             return EventResult::Ignored;
         }
 
-        match event {
-            //view keys
-            Event::Key(k) => match k {
-                Key::Left => {
-                    if self.cursor.x == 0 {
-                        return EventResult::Ignored;
-                    }
+        if let Event::Mouse {
+            offset,
+            position,
+            event,
+        } = event
+        {
+            self.pending_keys.clear();
+            return self.handle_mouse(position.saturating_sub(offset), event);
+        }
+
+        if let Some(pending) = self.pending.take() {
+            if let Some(res) = self.complete_pending(pending, &event) {
+                return res;
+            }
+        }
 
-                    self.cursor.x = self.cursor.x.saturating_sub(1);
+        self.pending_keys.push(event);
+        match self.keymap.lookup(&self.pending_keys) {
+            Lookup::Action(action) => {
+                self.pending_keys.clear();
+                self.perform_action(action)
+            }
+            Lookup::Prefix => EventResult::Consumed(None),
+            Lookup::Unbound => {
+                // an unfinished sequence is dropped, but its last key may start a new one
+                let event = self.pending_keys.pop().expect("the event has just been pushed");
+                if self.pending_keys.is_empty() {
+                    EventResult::Ignored
+                } else {
+                    self.pending_keys.clear();
+                    self.handle_event(event)
+                }
+            }
+        }
+    }
+
+    /// Runs the pending action with the argument typed as `event`, returns `None` if `event` is no valid argument
+    /// and should be handled as usual
+    ///
+    /// Bookmark names are consumed in any case, so an invalid name only cancels the action.
+    fn complete_pending(&mut self, pending: Pending, event: &Event) -> Option<EventResult> {
+        match pending {
+            Pending::Replace => {
+                let value = event.char()?.to_digit(16)?;
+                Some(self.set_nibble(value as u8, false))
+            }
+            Pending::SetBookmark => {
+                if let Some(c) = event.char() {
+                    let addr = self.cursor_addr();
+                    self.add_bookmark(c, addr);
                 }
-                Key::Right => {
-                    return self.cursor_x_advance();
+                Some(EventResult::Consumed(None))
+            }
+            Pending::JumpToBookmark => {
+                if let Some(c) = event.char() {
+                    self.jump_to_bookmark(c);
                 }
-                Key::Up => {
-                    if self.cursor.y == 0 {
-                        return EventResult::Ignored;
-                    }
+                Some(EventResult::Consumed(None))
+            }
+        }
+    }
+
+    /// Performs `action`, if the display state allows it
+    fn perform_action(&mut self, action: Action) -> EventResult {
+        match self.state {
+            DisplayState::Disabled => return EventResult::Ignored,
+            DisplayState::Enabled if action.is_edit() => return EventResult::Ignored,
+            _ => {}
+        }
 
-                    self.cursor.y = self.cursor.y.saturating_sub(1);
+        match action {
+            Action::MoveLeft => return self.cursor_x_retreat(),
+            Action::MoveRight => return self.cursor_x_advance(),
+            Action::MoveUp | Action::MoveDown => match self.get_next_line_row(action == Action::MoveUp) {
+                Some(row) => self.cursor_y_move(row),
+                None => return EventResult::Ignored,
+            },
+            Action::LineStart => self.set_cursor_pos((0, self.get_cursor_row()).into()),
+            Action::LineEnd => self.set_cursor_pos((usize::MAX, self.get_cursor_row()).into()),
+            Action::ViewStart => self.set_cursor_pos(Vec2::zero()),
+            Action::ViewEnd => self.set_cursor_pos(Vec2::max_value()),
+            Action::PageUp => return self.cursor_page_move(true),
+            Action::PageDown => return self.cursor_page_move(false),
+            Action::PrevGroup => return self.cursor_group_move(false),
+            Action::NextGroup => return self.cursor_group_move(true),
+            Action::DataStart => {
+                let first = self.regions.iter().find(|r| !r.is_empty()).map_or(0, Region::offset);
+                self.move_cursor_to(first);
+            }
+            Action::DataEnd => {
+                let last = self.regions.iter().rev().find_map(Region::last).unwrap_or(0);
+                self.move_cursor_to(last);
+            }
+
+            //jump actions
+            Action::NextNonZero | Action::PrevNonZero | Action::NextDifferent | Action::PrevDifferent => {
+                let current = self.get_element_under_cursor();
+                let found = match action {
+                    Action::NextNonZero => self.find_byte(true, |b| b != 0),
+                    Action::PrevNonZero => self.find_byte(false, |b| b != 0),
+                    Action::NextDifferent => self.find_byte(true, |b| Some(b) != current),
+                    _ => self.find_byte(false, |b| Some(b) != current),
+                };
+                match found {
+                    Some(offset) => self.move_cursor_to(offset),
+                    None => return EventResult::Ignored,
                 }
-                Key::Down => {
-                    if self.cursor.y == self.get_widget_height().saturating_sub(1) {
-                        return EventResult::Ignored;
-                    }
+            }
 
-                    let max_pos = min(self.data.len(), self.cursor.y / 2 + 16).saturating_sub(1);
-                    self.cursor.y = min(self.cursor.y + 1, max_pos);
-                    self.cursor.x = min(self.cursor.x, self.get_elements_in_current_row().saturating_sub(1) * 2);
+            //edit actions
+            Action::Grow => {
+                let last = self.regions.last().expect("there is always one region");
+                let edit = Edit::Insert {
+                    offset: last.end(),
+                    byte: 0,
+                };
+                if !self.apply_edit(edit) {
+                    return EventResult::Ignored;
                 }
-                Key::Home => self.cursor.x = 0,
-                Key::End => self.cursor.x = self.get_max_x_in_current_row(),
-                _ => {
+            }
+            Action::Shrink => {
+                let last = self.regions.last().and_then(Region::last);
+                return self.remove_byte(last);
+            }
+            Action::SetNibble(value) => return self.set_nibble(value, true),
+            Action::Replace => {
+                if self.get_element_under_cursor().is_none() {
                     return EventResult::Ignored;
                 }
-            },
-            Event::Shift(Key::Home) => self.cursor = (0, 0).into(),
-            Event::Shift(Key::End) => {
-                self.cursor = (
-                    get_max_x_in_row(self.data.len(), self.get_widget_height() - 1, 16),
-                    self.get_widget_height() - 1,
-                )
-                    .into();
-            }
-
-            //edit keys
-            Event::Char(c) => {
-                if self.state != DisplayState::Editable {
+                self.pending = Some(Pending::Replace);
+            }
+            Action::Delete => return self.remove_byte(Some(self.cursor.offset)),
+            Action::ToggleInsert => self.insert_mode = !self.insert_mode,
+            Action::Undo => {
+                if !self.undo() {
+                    return EventResult::Ignored;
+                }
+            }
+            Action::Redo => {
+                if !self.redo() {
                     return EventResult::Ignored;
                 }
+            }
 
-                match c {
-                    '+' => {
-                        let datalen = self.data.len();
-                        self.set_len(datalen + 1);
-                    }
-                    '-' => {
-                        let datalen = self.data.len();
-                        self.set_len(datalen.saturating_sub(1));
-                    }
-                    _ => {
-                        if let Some(val) = c.to_digit(16) {
-                            if let Some(dat) = self.get_element_under_cursor() {
-                                let realpos = self.cursor;
-                                let elem = realpos.y * self.config.bytes_per_line + realpos.x / 2;
-                                let high = self.cursor.x % 2 == 0;
-                                let mask = 0xF << if high { 4 } else { 0 };
-
-                                self.data[elem] = (dat & !mask) | ((val as u8) << if high { 4 } else { 0 });
-                                self.cursor_x_advance();
-                            }
-                        } else {
-                            return EventResult::Ignored;
-                        }
-                    }
+            //search actions
+            Action::FindNext => {
+                if !self.find_next() {
+                    return EventResult::Ignored;
                 }
             }
-            Event::Mouse {
-                offset,
-                position,
-                event: MouseEvent::Press(_),
-            } => {
-                if let Some(position) = position.checked_sub(offset) {
-                    self.cursor = self.convert_visual_to_real_cursor(position);
-                } else {
+            Action::FindPrev => {
+                if !self.find_prev() {
                     return EventResult::Ignored;
                 }
             }
-            _ => {
-                return EventResult::Ignored;
+
+            //bookmark actions
+            Action::SetBookmark => self.pending = Some(Pending::SetBookmark),
+            Action::JumpToBookmark => {
+                if self.bookmarks.is_empty() {
+                    return EventResult::Ignored;
+                }
+                self.pending = Some(Pending::JumpToBookmark);
             }
-        };
+            Action::JumpBack => {
+                if !self.jump_back() {
+                    return EventResult::Ignored;
+                }
+            }
+            Action::JumpForward => {
+                if !self.jump_forward() {
+                    return EventResult::Ignored;
+                }
+            }
+            Action::FollowPointer => {
+                if !self.follow_pointer() {
+                    return EventResult::Ignored;
+                }
+            }
+
+            Action::Help => return EventResult::with_cb(self.show_help()),
+            Action::Search | Action::Goto => {
+                return match self.on_command.clone() {
+                    Some(callback) => EventResult::with_cb(move |s| callback(s, action)),
+                    None => EventResult::Ignored,
+                };
+            }
+        }
 
         EventResult::Consumed(None)
     }
+}
 
-    fn required_size(&mut self, _: Vec2) -> Vec2 {
-        let length = self.get_field_length(Field::Addr)
-            + self.get_field_length(Field::AddrSep)
-            + self.get_field_length(Field::Hex)
-            + self.get_field_length(Field::AsciiSep)
-            + self.get_field_length(Field::Ascii);
+impl View for HexView {
+    fn on_event(&mut self, event: Event) -> EventResult {
+        if event == Event::Refresh {
+            self.poll_source_if_due();
+            self.publish_status();
+            return EventResult::Ignored;
+        }
+        let res = self.handle_event(event);
+        if res.is_consumed() {
+            self.scroll_to_cursor();
+            self.publish_status();
+        }
+        res
+    }
 
-        (length, self.get_widget_height()).into()
+    fn needs_relayout(&self) -> bool {
+        self.layout_changed
     }
 
-    fn draw(&self, printer: &Printer) {
-        let height = self.get_widget_height();
-        //they are a tuple of (offset, len)
-        let addr = (0usize, self.get_field_length(Field::Addr));
-        let addr_sep = (addr.0 + addr.1, self.get_field_length(Field::AddrSep));
-        let hex = (addr_sep.0 + addr_sep.1, self.get_field_length(Field::Hex));
-        let ascii_sep = (hex.0 + hex.1, self.get_field_length(Field::AsciiSep));
-        let ascii = (ascii_sep.0 + ascii_sep.1, self.get_field_length(Field::Ascii));
+    fn important_area(&self, view_size: Vec2) -> Rect {
+        let row = self.get_cursor_row().saturating_sub(self.v_scroll);
+        Rect::from_size((0, row), (view_size.x, 1))
+    }
 
-        self.draw_addr(&printer.offset((addr.0, 0)).cropped((addr.1, height)));
-        self.draw_addr_hex_sep(&printer.offset((addr_sep.0, 0)).cropped((addr_sep.1, height)));
-        self.draw_hex(&printer.offset((hex.0, 0)).cropped((hex.1, height)));
-        if self.config.show_ascii {
-            self.draw_ascii_sep(&printer.offset((ascii_sep.0, 0)).cropped((ascii_sep.1, height)));
-            self.draw_ascii(&printer.offset((ascii.0, 0)).cropped((ascii.1, height)));
+    fn layout(&mut self, size: Vec2) {
+        self.layout_changed = false;
+        self.size = Some(size);
+        let fitted = self.fit_bytes_per_line(size.x);
+        if fitted != self.fitted_bytes_per_line {
+            self.reflow(fitted);
         }
+        self.scroll_to_cursor();
+        self.publish_status();
+    }
+
+    fn required_size(&mut self, constraint: Vec2) -> Vec2 {
+        let bytes_per_line = self
+            .fit_bytes_per_line(constraint.x)
+            .unwrap_or_else(|| self.bytes_per_line());
+
+        (
+            self.get_layout_width(bytes_per_line),
+            RowMap::new(&self.regions, bytes_per_line).len(),
+        )
+            .into()
+    }
+
+    fn draw(&self, printer: &Printer) {
+        *self.viewport.lock().unwrap_or_else(PoisonError::into_inner) =
+            Some((printer.content_offset.y, printer.output_size.y));
+        let height = self.get_widget_height();
+        let layout = self.get_layout();
+        let pinned = get_pinned_width(&layout);
+        let printer = &printer.content_offset((0, self.v_scroll));
+        let scrolled = printer.offset((pinned, 0)).content_offset((self.h_scroll, 0));
+
+        for cell in layout {
+            let printer = if cell.x < pinned {
+                printer.offset((cell.x, 0))
+            } else {
+                scrolled.offset((cell.x - pinned, 0))
+            }
+            .cropped((cell.width, height));
+            match cell.part {
+                Part::Separator(separator) => self.draw_separator(&printer, separator),
+                Part::Column(column) => {
+                    match column.style {
+                        Some(style) => printer.with_color(style, |p| self.draw_column(p, &column.kind)),
+                        None => self.draw_column(&printer, &column.kind),
+                    }
 
-        if self.state != DisplayState::Disabled {
-            self.highlight_current_hex(&printer.offset((hex.0, 0)).cropped((hex.1, height)).focused(true));
-            if self.config.show_ascii {
-                self.highlight_current_ascii(&printer.offset((ascii.0, 0)).cropped((ascii.1, height)).focused(true));
+                    match column.kind {
+                        ColumnKind::Hex => {
+                            self.highlight_changes(&printer, true);
+                            self.highlight_selection(&printer, true);
+                        }
+                        ColumnKind::Text => {
+                            self.highlight_changes(&printer, false);
+                            self.highlight_selection(&printer, false);
+                        }
+                        _ => {}
+                    }
+                    if self.state != DisplayState::Disabled {
+                        match column.kind {
+                            ColumnKind::Hex => self.highlight_current_hex(&printer.focused(true)),
+                            ColumnKind::Text => self.highlight_current_ascii(&printer.focused(true)),
+                            _ => {}
+                        }
+                    }
+                }
             }
         }
     }
@@ -745,5 +2564,253 @@ impl View for HexView {
     }
 }
 
-//TODO: needs_relayout: only when cursor moved or data has been updated (either internally or externally)
-//      required_size:  support different views (e.g. wihtout ascii, without addr, hex only)
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn status_of_full_range_selection() {
+        let mut view = HexView::new_from_iter([0; 16]);
+        view.set_selection(0..=u64::MAX);
+        assert_eq!(view.status().selection_len, u64::MAX);
+
+        view.set_selection(4..=7);
+        assert_eq!(view.status().selection_len, 4);
+    }
+
+    #[test]
+    fn unknown_bytes_are_skipped() {
+        let regions = vec![Region::new_partial(
+            "disk",
+            0,
+            vec![Some(0x55), None, Some(0x55), Some(0xAA)],
+        )];
+        let mut view = HexView::new_from_regions(regions)
+            .unwrap()
+            .display_state(DisplayState::Editable);
+
+        view.perform(Action::NextDifferent);
+        assert_eq!(view.cursor_addr(), 3);
+
+        view.set_cursor_addr(1);
+        view.perform(Action::SetNibble(0xA));
+        assert!(!view.is_known(1));
+        assert!(!view.search(&[0x55, 0x00]));
+
+        // every known byte differs from an unknown one
+        view.perform(Action::PrevDifferent);
+        assert_eq!(view.cursor_addr(), 0);
+    }
+
+    #[test]
+    fn fit_bytes_per_line_to_width() {
+        let mut view = HexView::new_from_iter([0; 256]);
+        view.config_mut().bytes_per_group = 4;
+        assert_eq!(view.fit_bytes_per_line(80), None);
+
+        view.config_mut().fit_width = FitWidth::Groups;
+        let width = view.get_layout_width(8);
+        assert_eq!(view.fit_bytes_per_line(width), Some(8));
+        assert_eq!(view.fit_bytes_per_line(width - 1), Some(4));
+        // the smallest line is used if nothing fits
+        assert_eq!(view.fit_bytes_per_line(0), Some(4));
+
+        view.config_mut().fit_width = FitWidth::PowersOfTwo;
+        let width = view.get_layout_width(16);
+        assert_eq!(view.fit_bytes_per_line(width), Some(16));
+        assert_eq!(view.fit_bytes_per_line(width - 1), Some(8));
+        assert_eq!(view.fit_bytes_per_line(0), Some(1));
+    }
+
+    #[test]
+    fn reflow_keeps_cursor() {
+        let mut view = HexView::new_from_iter([0; 256]);
+        view.config_mut().fit_width = FitWidth::PowersOfTwo;
+        view.set_cursor_addr(20);
+        view.layout((view.get_layout_width(8), 10).into());
+        assert_eq!(view.bytes_per_line(), 8);
+        assert_eq!(view.cursor_addr(), 20);
+        assert_eq!(view.get_cursor_row(), 2);
+    }
+
+    /// Returns the x position of the hex or the text column
+    fn column_x(view: &HexView, hex: bool) -> usize {
+        let layout = view.get_layout();
+        let cell = layout.iter().find(|cell| match cell.kind() {
+            Some(ColumnKind::Hex) => hex,
+            Some(ColumnKind::Text) => !hex,
+            _ => false,
+        });
+        cell.expect("the column is shown").x
+    }
+
+    /// Sends a mouse event and returns whether it was consumed
+    fn mouse(view: &mut HexView, position: (usize, usize), event: MouseEvent) -> bool {
+        view.on_event(Event::Mouse {
+            offset: Vec2::zero(),
+            position: position.into(),
+            event,
+        })
+        .is_consumed()
+    }
+
+    #[test]
+    fn drag_selects_bytes() {
+        let mut view = HexView::new_from_iter([0; 256]).display_state(DisplayState::Enabled);
+        view.layout((80, 4).into());
+        let hex = column_x(&view, true);
+
+        mouse(&mut view, (hex + 3 * 3, 0), MouseEvent::Press(MouseButton::Left));
+        assert_eq!(view.cursor_addr(), 3);
+        mouse(&mut view, (hex + 5 * 3 + 1, 1), MouseEvent::Hold(MouseButton::Left));
+        assert_eq!(view.cursor_addr(), 21);
+        assert_eq!(view.selection(), Some(3..=21));
+
+        // dragging back to the start selects nothing
+        mouse(&mut view, (hex + 3 * 3, 0), MouseEvent::Hold(MouseButton::Left));
+        assert_eq!(view.selection(), None);
+
+        mouse(&mut view, (hex, 1), MouseEvent::Hold(MouseButton::Left));
+        mouse(&mut view, (hex, 1), MouseEvent::Release(MouseButton::Left));
+        assert!(!mouse(&mut view, (hex, 2), MouseEvent::Hold(MouseButton::Left)));
+        assert_eq!(view.selection(), Some(3..=16));
+
+        // other buttons only move the cursor
+        mouse(&mut view, (hex, 3), MouseEvent::Press(MouseButton::Right));
+        assert_eq!(view.cursor_addr(), 48);
+        assert_eq!(view.selection(), Some(3..=16));
+    }
+
+    #[test]
+    fn double_click_selects_group() {
+        let mut view = HexView::new_from_iter([0; 256]).display_state(DisplayState::Enabled);
+        view.config_mut().bytes_per_group = 4;
+        view.layout((80, 4).into());
+        let hex = column_x(&view, true);
+
+        // the second digit of the byte at 5, in the second group
+        let pos = (hex + 4 * 2 + 1 + 3, 0);
+        mouse(&mut view, pos, MouseEvent::Press(MouseButton::Left));
+        mouse(&mut view, pos, MouseEvent::Release(MouseButton::Left));
+        assert_eq!(view.selection(), None);
+        mouse(&mut view, pos, MouseEvent::Press(MouseButton::Left));
+        assert_eq!(view.selection(), Some(4..=7));
+        assert_eq!(view.cursor_addr(), 5);
+    }
+
+    #[test]
+    fn click_into_text_column() {
+        let mut view = HexView::new_from_iter([0; 256]).display_state(DisplayState::Enabled);
+        view.layout((80, 4).into());
+        let text = column_x(&view, false);
+
+        mouse(&mut view, (text + 7, 2), MouseEvent::Press(MouseButton::Left));
+        assert_eq!(view.cursor_addr(), 39);
+        assert_eq!(view.cursor.digit, 0);
+    }
+
+    #[test]
+    fn wheel_scrolls_within_data() {
+        let mut view = HexView::new_from_iter([0; 256]).display_state(DisplayState::Enabled);
+        view.layout((80, 4).into());
+
+        assert!(!mouse(&mut view, (0, 0), MouseEvent::WheelUp));
+        mouse(&mut view, (0, 0), MouseEvent::WheelDown);
+        assert_eq!(view.v_scroll, WHEEL_ROWS);
+        for _ in 0..5 {
+            mouse(&mut view, (0, 0), MouseEvent::WheelDown);
+        }
+        assert_eq!(view.v_scroll, 16 - 4);
+        assert!(!mouse(&mut view, (0, 0), MouseEvent::WheelDown));
+        // the cursor stays where it is
+        assert_eq!(view.cursor_addr(), 0);
+    }
+
+    #[test]
+    fn important_area_is_cursor_row_on_screen() {
+        let mut view = HexView::new_from_iter([0; 256]);
+        view.set_cursor_addr(0x80);
+        view.layout((80, 4).into());
+        assert_eq!(view.important_area((80, 4).into()), Rect::from_size((0, 3), (80, 1)));
+
+        // separators count as rows
+        let regions = vec![Region::new("a", 0, vec![0; 16]), Region::new("b", 0x100, vec![0; 16])];
+        let mut view = HexView::new_from_regions(regions).unwrap();
+        view.set_cursor_addr(0x105);
+        view.layout((80, 10).into());
+        assert_eq!(view.important_area((80, 10).into()), Rect::from_size((0, 2), (80, 1)));
+    }
+
+    #[test]
+    fn relayout_only_after_resize() {
+        let mut view = HexView::new_from_iter([0; 16]).display_state(DisplayState::Editable);
+        view.layout((80, 4).into());
+        assert!(!view.needs_relayout());
+
+        view.perform(Action::MoveDown);
+        view.perform(Action::SetNibble(0xA));
+        assert!(!view.needs_relayout());
+
+        view.perform(Action::Grow);
+        assert!(view.needs_relayout());
+        view.layout((80, 4).into());
+        assert!(!view.needs_relayout());
+    }
+
+    #[test]
+    fn key_sequences() {
+        let mut view = HexView::new_from_iter([0; 256])
+            .display_state(DisplayState::Enabled)
+            .with_keymap(Keymap::vim());
+        view.layout((80, 4).into());
+        view.set_cursor_addr(0x42);
+
+        assert!(view.on_event(Event::Char('g')).is_consumed());
+        assert_eq!(view.cursor_addr(), 0x42);
+        view.on_event(Event::Char('g'));
+        assert_eq!(view.cursor_addr(), 0);
+
+        // an unfinished sequence is dropped and the last key is handled on its own
+        view.on_event(Event::Char('g'));
+        view.on_event(Event::Char('j'));
+        assert_eq!(view.cursor_addr(), 0x10);
+        view.on_event(Event::Char('g'));
+        assert!(!view.on_event(Event::Char('z')).is_consumed());
+        assert!(view.pending_keys.is_empty());
+
+        // a mouse event cancels the sequence
+        view.on_event(Event::Char('g'));
+        view.on_event(Event::Mouse {
+            offset: Vec2::zero(),
+            position: Vec2::zero(),
+            event: MouseEvent::Release(MouseButton::Left),
+        });
+        view.on_event(Event::Char('g'));
+        assert_eq!(view.cursor_addr(), 0x10);
+    }
+
+    #[test]
+    fn overlapping_regions_are_rejected() {
+        let mut view = HexView::new_from_iter(b"XYZ");
+        assert!(view
+            .set_regions(vec![Region::new("a", 0, b"ABC"), Region::new("b", 2, b"DEF")])
+            .is_err());
+        assert_eq!(view.data(), b"XYZ");
+    }
+
+    #[test]
+    fn set_len_of_several_regions() {
+        let regions = vec![
+            Region::new("a", 0, b"ABC"),
+            Region::new("b", 0x10, b"DEF"),
+            Region::new("c", 0x20, b"G"),
+        ];
+        let mut view = HexView::new_from_regions(regions).unwrap();
+        view.set_len(9);
+        assert_eq!(view.regions()[2], Region::new("c", 0x20, b"G\0\0"));
+
+        // regions which end up empty are removed
+        view.set_len(2);
+        assert_eq!(view.regions(), &[Region::new("a", 0, b"AB")]);
+    }
+}
@@ -0,0 +1,198 @@
+use std::error::Error;
+use std::fmt;
+
+use region::Region;
+use HexView;
+
+/// Describes what went wrong while parsing a textual representation of data.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ParseErrorKind {
+    /// The line does not start with the start code of the format (e.g. `:` for Intel HEX).
+    InvalidStartCode,
+    /// A character which is not a hexadecimal digit was found.
+    InvalidHexDigit(char),
+    /// The line ended in the middle of a record.
+    UnexpectedEndOfLine,
+    /// The number of bytes in a record does not match its length field.
+    LengthMismatch {
+        /// The number of bytes the record should have
+        expected: usize,
+        /// The number of bytes the record has
+        found: usize,
+    },
+    /// The checksum of a record is wrong.
+    ChecksumMismatch {
+        /// The checksum calculated from the record
+        expected: u8,
+        /// The checksum stored in the record
+        found: u8,
+    },
+    /// The record type is unknown or not supported.
+    UnsupportedRecord(String),
+    /// The byte at the given address has already been defined by an earlier record.
+    OverlappingData(u64),
+    /// A character which is not allowed at this position was found.
+    UnexpectedCharacter(char),
+    /// An address is missing or does not fit into 64 bits.
+    InvalidAddress,
+}
+
+impl fmt::Display for ParseErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidStartCode => write!(f, "invalid start code"),
+            Self::InvalidHexDigit(c) => write!(f, "invalid hex digit {c:?}"),
+            Self::UnexpectedEndOfLine => write!(f, "unexpected end of line"),
+            Self::LengthMismatch { expected, found } => {
+                write!(f, "record should contain {expected} bytes, but contains {found}")
+            }
+            Self::ChecksumMismatch { expected, found } => {
+                write!(f, "checksum mismatch, expected {expected:02X} but found {found:02X}")
+            }
+            Self::UnsupportedRecord(record) => write!(f, "unsupported record type {record}"),
+            Self::OverlappingData(addr) => write!(f, "data at address {addr:X} is defined twice"),
+            Self::UnexpectedCharacter(c) => write!(f, "unexpected character {c:?}"),
+            Self::InvalidAddress => write!(f, "invalid address"),
+        }
+    }
+}
+
+/// An error which occured while parsing a textual representation of data.
+///
+/// It carries the position of the error, both line and column start counting at 1.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ParseError {
+    line: usize,
+    column: usize,
+    kind: ParseErrorKind,
+}
+
+impl ParseError {
+    pub(crate) const fn new(line: usize, column: usize, kind: ParseErrorKind) -> Self {
+        Self { line, column, kind }
+    }
+
+    /// Returns the line in which the error occured.
+    #[must_use]
+    pub const fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Returns the column in which the error occured.
+    #[must_use]
+    pub const fn column(&self) -> usize {
+        self.column
+    }
+
+    /// Returns what went wrong.
+    #[must_use]
+    pub const fn kind(&self) -> &ParseErrorKind {
+        &self.kind
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}: {}", self.line, self.column, self.kind)
+    }
+}
+
+impl Error for ParseError {}
+
+/// A single line of a record based format, which keeps track of the columns for error reporting
+pub(crate) struct Record<'a> {
+    line: usize,
+    text: &'a str,
+    /// column (starting at 0) of the first hex digit
+    hex_start: usize,
+}
+
+impl<'a> Record<'a> {
+    /// Strips `start_code` from `text`, which is line number `line`
+    pub(crate) fn new(line: usize, text: &'a str, start_code: char) -> Result<Self, ParseError> {
+        if !text.starts_with(start_code) {
+            return Err(ParseError::new(line, 1, ParseErrorKind::InvalidStartCode));
+        }
+        Ok(Self {
+            line,
+            text,
+            hex_start: start_code.len_utf8(),
+        })
+    }
+
+    /// Returns the characters between the start code and the hex digits
+    pub(crate) fn take_chars(&mut self, count: usize) -> Result<&'a str, ParseError> {
+        let end = self.hex_start + count;
+        let res = self
+            .text
+            .get(self.hex_start..end)
+            .ok_or_else(|| self.error_at(self.text.len(), ParseErrorKind::UnexpectedEndOfLine))?;
+        self.hex_start = end;
+        Ok(res)
+    }
+
+    /// Decodes the hex digits after the start code
+    pub(crate) fn bytes(&self) -> Result<Vec<u8>, ParseError> {
+        decode_hex(&self.text[self.hex_start..]).map_err(|(pos, kind)| self.error_at(self.hex_start + pos, kind))
+    }
+
+    /// Creates an error located at the column (starting at 0) `column`
+    pub(crate) fn error_at(&self, column: usize, kind: ParseErrorKind) -> ParseError {
+        ParseError::new(self.line, column + 1, kind)
+    }
+
+    /// Creates an error located at the byte with `index` as returned by [`bytes`](#method.bytes)
+    pub(crate) fn error_at_byte(&self, index: usize, kind: ParseErrorKind) -> ParseError {
+        self.error_at(self.hex_start + 2 * index, kind)
+    }
+}
+
+/// Decodes a string of hex digit pairs, returning the position of the offending character on error
+pub(crate) fn decode_hex(text: &str) -> Result<Vec<u8>, (usize, ParseErrorKind)> {
+    let digits = text
+        .char_indices()
+        .map(|(pos, c)| c.to_digit(16).ok_or((pos, ParseErrorKind::InvalidHexDigit(c))))
+        .collect::<Result<Vec<_>, _>>()?;
+    if digits.len() % 2 != 0 {
+        return Err((text.len(), ParseErrorKind::UnexpectedEndOfLine));
+    }
+    Ok(digits.chunks(2).map(|d| (d[0] << 4 | d[1]) as u8).collect())
+}
+
+/// A run of bytes at an absolute address, as found in line `line` of the input
+#[derive(Debug)]
+pub(crate) struct Chunk {
+    pub(crate) addr: u64,
+    pub(crate) line: usize,
+    pub(crate) data: Vec<u8>,
+}
+
+/// Builds a view from chunks of data in any order.
+///
+/// The lowest address becomes the start address of the view, adjacent chunks are merged into one region.
+pub(crate) fn build_view(mut chunks: Vec<Chunk>) -> Result<HexView, ParseError> {
+    chunks.retain(|c| !c.data.is_empty());
+    chunks.sort_by_key(|c| c.addr);
+
+    let start = chunks.first().map_or(0, |c| c.addr);
+    let mut regions: Vec<(u64, Vec<u8>)> = Vec::new();
+    for chunk in chunks {
+        let offset = chunk.addr - start;
+        match regions.last_mut() {
+            Some((first, data)) if first.saturating_add(data.len() as u64) > offset => {
+                return Err(ParseError::new(
+                    chunk.line,
+                    1,
+                    ParseErrorKind::OverlappingData(chunk.addr),
+                ));
+            }
+            Some((first, data)) if first.saturating_add(data.len() as u64) == offset => data.extend(chunk.data),
+            _ => regions.push((offset, chunk.data)),
+        }
+    }
+
+    let mut view = HexView::new_from_regions(regions.into_iter().map(|(offset, data)| Region::new("", offset, data)))
+        .expect("regions are sorted and do not overlap");
+    view.config_mut().start_addr = start;
+    Ok(view)
+}
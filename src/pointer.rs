@@ -0,0 +1,165 @@
+use cursive::With;
+
+use HexView;
+
+/// The number of bytes of a pointer, see [`PointerFormat`].
+///
+/// [`PointerFormat`]: struct.PointerFormat.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PointerWidth {
+    /// A single byte
+    Bits8,
+    /// Two bytes
+    Bits16,
+    /// Four bytes
+    Bits32,
+    /// Eight bytes
+    Bits64,
+}
+
+impl PointerWidth {
+    /// Returns the number of bytes of a pointer of this width.
+    #[must_use]
+    pub const fn bytes(self) -> usize {
+        match self {
+            Self::Bits8 => 1,
+            Self::Bits16 => 2,
+            Self::Bits32 => 4,
+            Self::Bits64 => 8,
+        }
+    }
+}
+
+/// Describes how the bytes under the cursor are read as a pointer, see [`HexView::follow_pointer`].
+///
+/// The address the pointer points to is its value plus the `base`, so a base of `0` reads absolute addresses,
+/// while e.g. the address of a file header reads offsets relative to that header.
+///
+/// [`HexView::follow_pointer`]: struct.HexView.html#method.follow_pointer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PointerFormat {
+    /// Controls the number of bytes of a pointer.
+    pub width: PointerWidth,
+    /// Controls whether the most significant byte comes first.
+    pub big_endian: bool,
+    /// Controls the address which is added to the value of a pointer.
+    pub base: u64,
+}
+
+impl Default for PointerFormat {
+    /// Reads absolute, 32 bit little endian pointers.
+    fn default() -> Self {
+        Self {
+            width: PointerWidth::Bits32,
+            big_endian: false,
+            base: 0,
+        }
+    }
+}
+
+impl PointerFormat {
+    /// Returns the address which `bytes` point to, `None` if there are not enough bytes
+    fn target(&self, bytes: &[u8]) -> Option<u64> {
+        let bytes = bytes.get(..self.width.bytes())?;
+        let value = if self.big_endian {
+            bytes.iter().fold(0, |value, &b| value << 8 | u64::from(b))
+        } else {
+            bytes.iter().rev().fold(0, |value, &b| value << 8 | u64::from(b))
+        };
+        Some(self.base.wrapping_add(value))
+    }
+}
+
+// implements following pointers stored in the data
+impl HexView {
+    /// Returns how pointers are read by [`follow_pointer`](#method.follow_pointer).
+    #[must_use]
+    pub const fn pointer_format(&self) -> PointerFormat {
+        self.pointer_format
+    }
+
+    /// Sets how pointers are read by [`follow_pointer`](#method.follow_pointer).
+    pub fn set_pointer_format(&mut self, format: PointerFormat) {
+        self.pointer_format = format;
+    }
+
+    /// [`set_pointer_format`](#method.set_pointer_format)
+    #[must_use]
+    pub fn with_pointer_format(self, format: PointerFormat) -> Self {
+        self.with(|v| v.set_pointer_format(format))
+    }
+
+    /// Returns the address the bytes starting at the cursor point to, according to the
+    /// [`pointer_format`](#method.pointer_format).
+    ///
+    /// Returns `None` if any of the bytes is unmapped or unknown.
+    #[must_use]
+    pub fn pointer_target(&self) -> Option<u64> {
+        let bytes = self.get_bytes(self.cursor.offset, self.pointer_format.width.bytes());
+        let bytes: Option<Vec<u8>> = bytes.into_iter().collect();
+        self.pointer_format.target(&bytes?)
+    }
+
+    /// Moves the cursor to the address the bytes starting at the cursor point to and records the jump, so
+    /// [`jump_back`](#method.jump_back) returns to the pointer.
+    ///
+    /// Returns `false` if the pointer cannot be read or points to an unmapped byte, the cursor stays in place then.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cursive_hexview::{HexView, PointerFormat, PointerWidth};
+    /// let mut view = HexView::new_from_iter(vec![0x00, 0x06, 0x02, 0, 0, 0, 0xAA, 0xBB]);
+    /// view.set_pointer_format(PointerFormat {
+    ///     width: PointerWidth::Bits16,
+    ///     big_endian: true,
+    ///     base: 0,
+    /// });
+    /// assert!(view.follow_pointer());
+    /// assert_eq!(view.cursor_addr(), 6);
+    /// ```
+    pub fn follow_pointer(&mut self) -> bool {
+        match self.pointer_target() {
+            Some(addr) if self.is_mapped(addr.wrapping_sub(self.config.start_addr)) => {
+                self.jump_to(addr);
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Region;
+
+    #[test]
+    fn follow_pointer_with_base() {
+        let mut view = HexView::new_from_iter(vec![0x00, 0x06, 0x02, 0, 0, 0, 0xAA, 0xBB]);
+        view.set_cursor_addr(2);
+        view.set_pointer_format(PointerFormat {
+            width: PointerWidth::Bits8,
+            big_endian: false,
+            base: 5,
+        });
+        assert!(view.follow_pointer());
+        assert_eq!(view.cursor_addr(), 7);
+
+        // there are not enough bytes left for a 32 bit pointer
+        view.set_pointer_format(PointerFormat::default());
+        assert!(!view.follow_pointer());
+        assert_eq!(view.cursor_addr(), 7);
+
+        assert!(view.jump_back());
+        assert_eq!(view.cursor_addr(), 2);
+    }
+
+    #[test]
+    fn pointer_at_end_of_address_space() {
+        let mut view = HexView::new_from_regions(vec![Region::new("top", u64::MAX - 1, vec![0xFF; 2])]).unwrap();
+        view.set_cursor_addr(u64::MAX - 1);
+        assert_eq!(view.pointer_target(), None);
+        assert!(!view.follow_pointer());
+    }
+}
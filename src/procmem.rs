@@ -0,0 +1,241 @@
+use std::cmp::min;
+use std::convert::TryFrom;
+use std::fs::{self, File, OpenOptions};
+use std::io;
+use std::os::unix::fs::FileExt;
+
+use source::DataSource;
+use {HexView, HexViewConfig, Region};
+
+/// The granularity in which the kernel maps memory, so each page is read on its own
+const PAGE_SIZE: u64 = 4096;
+
+/// The maximum number of bytes shown of a single mapping, as a view holds all of its bytes in memory
+const MAX_MAPPING_LEN: u64 = 64 * 1024 * 1024;
+
+/// A memory mapping of a process, as listed in `/proc/<pid>/maps`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Mapping {
+    /// The address of the first byte
+    pub start: u64,
+    /// The address one past the last byte
+    pub end: u64,
+    /// The permissions, e.g. `r-xp` for private, executable code
+    pub perms: String,
+    /// The mapped file or a pseudo path like `[stack]`, empty for anonymous memory
+    pub path: String,
+}
+
+impl Mapping {
+    /// Checks whether the process may read the mapping.
+    #[must_use]
+    pub fn is_readable(&self) -> bool {
+        self.perms.starts_with('r')
+    }
+
+    /// Parses a line of `/proc/<pid>/maps`
+    fn parse(line: &str) -> Option<Self> {
+        let mut fields = line.splitn(6, ' ');
+        let (start, end) = fields.next()?.split_once('-')?;
+        let perms = fields.next()?.to_owned();
+        // offset, device and inode
+        fields.nth(2)?;
+        Some(Self {
+            start: u64::from_str_radix(start, 16).ok()?,
+            end: u64::from_str_radix(end, 16).ok()?,
+            perms,
+            path: fields.next().unwrap_or("").trim_start().to_owned(),
+        })
+    }
+}
+
+/// A [`DataSource`] which reads the memory of another process through `/proc/<pid>/mem`, Linux only.
+///
+/// Reading needs the same permissions as attaching a debugger, which usually means that the process is a child of
+/// the caller or that the caller runs as root. Edits are written back if `/proc/<pid>/mem` could be opened for
+/// writing.
+///
+/// [`DataSource`]: trait.DataSource.html
+///
+/// # Examples
+///
+/// ```
+/// # use std::io::{BufRead, BufReader, Write};
+/// # use std::process::{Command, Stdio};
+/// # use cursive_hexview::{DataSource, ProcessMemory};
+/// let mut child = Command::new("cat").stdin(Stdio::piped()).stdout(Stdio::piped()).spawn().unwrap();
+/// # // wait until the child runs, so that its executable is mapped
+/// # writeln!(child.stdin.as_mut().unwrap(), "ready").unwrap();
+/// # BufReader::new(child.stdout.as_mut().unwrap()).read_line(&mut String::new()).unwrap();
+/// let mut memory = ProcessMemory::open(child.id()).unwrap();
+///
+/// // the executable starts with its ELF header
+/// let path = std::fs::read_link(format!("/proc/{}/exe", child.id())).unwrap();
+/// let mappings = memory.mappings().unwrap();
+/// let exe = mappings.iter().find(|m| path.ends_with(&m.path)).unwrap();
+/// assert_eq!(memory.read(exe.start, 4), [0x7F, b'E', b'L', b'F'].map(Some));
+///
+/// child.kill().unwrap();
+/// child.wait().unwrap();
+/// ```
+#[derive(Debug)]
+pub struct ProcessMemory {
+    pid: u32,
+    mem: File,
+    writable: bool,
+}
+
+impl ProcessMemory {
+    /// Opens the memory of the process `pid`, for writing if permitted and for reading only otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the process does not exist or its memory may not be read.
+    pub fn open(pid: u32) -> io::Result<Self> {
+        let path = format!("/proc/{pid}/mem");
+        let (mem, writable) = match OpenOptions::new().read(true).write(true).open(&path) {
+            Ok(mem) => (mem, true),
+            Err(_) => (File::open(&path)?, false),
+        };
+        Ok(Self { pid, mem, writable })
+    }
+
+    /// Returns the id of the process.
+    #[must_use]
+    pub const fn pid(&self) -> u32 {
+        self.pid
+    }
+
+    /// Checks whether edits can be written back to the process.
+    #[must_use]
+    pub const fn is_writable(&self) -> bool {
+        self.writable
+    }
+
+    /// Returns the current memory mappings of the process, sorted by address.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `/proc/<pid>/maps` cannot be read, e.g. because the process has exited.
+    pub fn mappings(&self) -> io::Result<Vec<Mapping>> {
+        let maps = fs::read_to_string(format!("/proc/{}/maps", self.pid))?;
+        maps.lines()
+            .map(|line| {
+                Mapping::parse(line)
+                    .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, format!("invalid mapping {line:?}")))
+            })
+            .collect()
+    }
+
+    /// Creates a view with one region per readable mapping, which polls this process for its bytes.
+    ///
+    /// The [`start_addr`] is the address of the first mapping, so the view shows the real virtual addresses.
+    /// Each region is named after its mapped file. Pages which cannot be read are displayed as `??`.
+    ///
+    /// Mappings can be huge reservations of which only a small part is in use, e.g. heaps of virtual machines.
+    /// Only the first 64 MiB of each mapping are shown.
+    ///
+    /// [`start_addr`]: struct.HexViewConfig.html#structfield.start_addr
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the mappings cannot be read, or if a mapping does not fit into the address space of
+    /// this process.
+    pub fn into_view(self) -> io::Result<HexView> {
+        let mappings: Vec<_> = self.mappings()?.into_iter().filter(Mapping::is_readable).collect();
+        let start_addr = mappings.first().map_or(0, |m| m.start);
+        let regions = mappings
+            .into_iter()
+            .map(|m| {
+                let len = min(m.end.saturating_sub(m.start), MAX_MAPPING_LEN);
+                let len = usize::try_from(len).map_err(|_| {
+                    io::Error::new(io::ErrorKind::InvalidData, format!("mapping {:?} is too large", m.path))
+                })?;
+                Ok(Region::new(m.path, m.start - start_addr, vec![0; len]))
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+        let view = HexView::new_from_regions(regions)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?
+            .with_config(HexViewConfig {
+                start_addr,
+                ..HexViewConfig::default()
+            });
+        Ok(view.with_source(self))
+    }
+
+    /// Reads as many bytes as possible at `addr`, returns the number of bytes read
+    fn read_at(&self, buf: &mut [u8], addr: u64) -> usize {
+        let mut done = 0;
+        while done < buf.len() {
+            match self.mem.read_at(&mut buf[done..], addr + done as u64) {
+                Ok(0) => break,
+                Ok(n) => done += n,
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(_) => break,
+            }
+        }
+        done
+    }
+}
+
+impl DataSource for ProcessMemory {
+    fn read(&mut self, addr: u64, len: usize) -> Vec<Option<u8>> {
+        let mut bytes = Vec::with_capacity(len);
+        let mut page = [0; PAGE_SIZE as usize];
+        while bytes.len() < len {
+            let Some(page_addr) = addr.checked_add(bytes.len() as u64) else {
+                break;
+            };
+            let page_len = min((PAGE_SIZE - page_addr % PAGE_SIZE) as usize, len - bytes.len());
+            let read = self.read_at(&mut page[..page_len], page_addr);
+            bytes.extend(page[..read].iter().copied().map(Some));
+            // the rest of the page cannot be read
+            bytes.resize(bytes.len() + page_len - read, None);
+        }
+        bytes
+    }
+
+    fn write(&mut self, addr: u64, data: &[u8]) -> io::Result<()> {
+        if !self.writable {
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                "the memory is read-only",
+            ));
+        }
+        self.mem.write_all_at(data, addr)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::process;
+
+    use super::*;
+
+    #[test]
+    fn parse_mappings() {
+        let mapping = Mapping::parse("7f12a000-7f12b000 r-xp 00001000 08:01 1234       /usr/lib/libc.so.6").unwrap();
+        assert_eq!(mapping.start, 0x7f12_a000);
+        assert_eq!(mapping.end, 0x7f12_b000);
+        assert_eq!(mapping.path, "/usr/lib/libc.so.6");
+        assert!(mapping.is_readable());
+
+        let mapping = Mapping::parse("7f12b000-7f12c000 ---p 00000000 00:00 0").unwrap();
+        assert_eq!(mapping.path, "");
+        assert!(!mapping.is_readable());
+
+        assert_eq!(Mapping::parse("7f12b000 ---p 00000000 00:00 0"), None);
+        assert_eq!(Mapping::parse("7f12b000-7f12c000 ---p"), None);
+    }
+
+    #[test]
+    fn read_and_write_own_memory() {
+        let mut memory = ProcessMemory::open(process::id()).unwrap();
+        assert_eq!(memory.read(0, 2), [None, None]);
+
+        let buf = Box::new([0u8; 3]);
+        let addr = buf.as_ptr() as u64;
+        memory.write(addr, b"hex").unwrap();
+        assert_eq!(memory.read(addr, 3), b"hex".map(Some));
+    }
+}
@@ -0,0 +1,475 @@
+use std::borrow::Borrow;
+use std::cmp::{max, min};
+use std::error::Error;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::iter;
+
+/// A contiguous, named block of bytes inside the address space of a [`HexView`].
+///
+/// The offset of a region is relative to the [`start_addr`] of the view, so changing the start address moves
+/// all regions at once. The space between two regions is unmapped. Unmapped bytes which share a line with
+/// mapped ones are displayed as `??`, lines without any mapped byte are collapsed into a single separator row.
+///
+/// A mapped byte may still be unknown, e.g. because it could not be read. Unknown bytes are displayed as `??`
+/// as well, but keep their place in the region, see [`new_partial`](#method.new_partial).
+///
+/// [`HexView`]: struct.HexView.html
+/// [`start_addr`]: struct.HexViewConfig.html#structfield.start_addr
+///
+/// # Examples
+///
+/// ```
+/// # use cursive_hexview::Region;
+/// let flash = Region::new("flash", 0x0800_0000, vec![0xFF; 64]);
+/// assert_eq!(flash.name(), "flash");
+/// assert_eq!(flash.offset(), 0x0800_0000);
+/// assert_eq!(flash.end(), 0x0800_0040);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Region {
+    name: String,
+    offset: u64,
+    data: Vec<u8>,
+    /// whether each byte holds a value which has been read, empty if all of them are known
+    known: Vec<bool>,
+    /// the number of dropped bytes at the front of `data` and `known`, which are removed in bulk
+    head: usize,
+}
+
+impl Region {
+    /// Creates a new region called `name`, which starts `offset` bytes after the start address of the view.
+    pub fn new<S: Into<String>, B: Borrow<u8>, I: IntoIterator<Item = B>>(name: S, offset: u64, data: I) -> Self {
+        Self {
+            name: name.into(),
+            offset,
+            data: data.into_iter().map(|u| *u.borrow()).collect(),
+            known: Vec::new(),
+            head: 0,
+        }
+    }
+
+    /// Creates a new region like [`new`](#method.new), in which the bytes given as `None` are unknown.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cursive_hexview::Region;
+    /// let sector = Region::new_partial("disk", 0, vec![Some(0x55), None, Some(0xAA)]);
+    /// assert_eq!(sector.len(), 3);
+    /// assert_eq!(sector.get(0), Some(0x55));
+    /// assert_eq!(sector.get(1), None);
+    /// assert!(!sector.is_known(1));
+    /// ```
+    pub fn new_partial<S: Into<String>, I: IntoIterator<Item = Option<u8>>>(name: S, offset: u64, bytes: I) -> Self {
+        let (data, known): (Vec<_>, Vec<_>) = bytes.into_iter().map(|b| (b.unwrap_or(0), b.is_some())).unzip();
+        let known = if known.iter().all(|&k| k) { Vec::new() } else { known };
+        Self {
+            name: name.into(),
+            offset,
+            data,
+            known,
+            head: 0,
+        }
+    }
+
+    /// Returns the name of this region.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the offset of the first byte of this region.
+    #[must_use]
+    pub const fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Returns the offset one past the last byte of this region.
+    ///
+    /// For a region which touches the end of the address space, this saturates at `u64::MAX`.
+    #[must_use]
+    pub fn end(&self) -> u64 {
+        self.offset.saturating_add(self.len() as u64)
+    }
+
+    /// Returns a reference to the data of this region.
+    ///
+    /// Unknown bytes are included with an arbitrary value, check them with [`is_known`](#method.is_known).
+    #[must_use]
+    pub fn data(&self) -> &[u8] {
+        &self.data[self.head..]
+    }
+
+    /// Returns the number of bytes in this region.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.data.len() - self.head
+    }
+
+    /// Checks whether this region contains no bytes.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the offset of the last byte, or `None` if the region is empty
+    pub(crate) fn last(&self) -> Option<u64> {
+        (self.len() as u64)
+            .checked_sub(1)
+            .map(|l| self.offset.saturating_add(l))
+    }
+
+    /// Checks whether the byte at `offset` belongs to this region
+    pub(crate) fn contains(&self, offset: u64) -> bool {
+        offset >= self.offset && offset - self.offset < self.len() as u64
+    }
+
+    /// Checks whether the value of the byte at `index` is known.
+    ///
+    /// Indices outside of the region are never known.
+    #[must_use]
+    pub fn is_known(&self, index: usize) -> bool {
+        index < self.len() && self.known.get(self.head + index).copied().unwrap_or(true)
+    }
+
+    /// Returns the byte at `index`, or `None` if it is unknown or outside of the region.
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<u8> {
+        self.is_known(index).then(|| self.data[self.head + index])
+    }
+
+    /// Returns the runs of consecutive known bytes, each with the index of its first byte
+    pub(crate) fn known_runs(&self) -> impl Iterator<Item = (usize, &[u8])> + '_ {
+        let mut pos = 0;
+        iter::from_fn(move || {
+            let start = (pos..self.len()).find(|&i| self.is_known(i))?;
+            let end = (start..self.len()).find(|&i| !self.is_known(i)).unwrap_or(self.len());
+            pos = end;
+            Some((start, &self.data()[start..end]))
+        })
+    }
+
+    /// Marks all bytes as unknown, until they are set again
+    pub(crate) fn forget(&mut self) {
+        self.known = vec![false; self.data.len()];
+    }
+
+    /// Sets the byte at index `pos` and marks it as known
+    pub(crate) fn set(&mut self, pos: usize, byte: u8) {
+        self.data[self.head + pos] = byte;
+        if let Some(known) = self.known.get_mut(self.head + pos) {
+            *known = true;
+        }
+    }
+
+    /// Marks the byte at index `pos` as unknown
+    pub(crate) fn set_unknown(&mut self, pos: usize) {
+        if self.known.is_empty() {
+            self.known = vec![true; self.data.len()];
+        }
+        self.known[self.head + pos] = false;
+    }
+
+    /// Inserts a known byte at index `pos`
+    pub(crate) fn insert(&mut self, pos: usize, byte: u8) {
+        self.data.insert(self.head + pos, byte);
+        if !self.known.is_empty() {
+            self.known.insert(self.head + pos, true);
+        }
+    }
+
+    /// Removes the byte at index `pos`
+    pub(crate) fn remove(&mut self, pos: usize) -> u8 {
+        if !self.known.is_empty() {
+            self.known.remove(self.head + pos);
+        }
+        self.data.remove(self.head + pos)
+    }
+
+    /// Grows or shrinks the region to `len` bytes, new bytes are known zeros
+    pub(crate) fn resize(&mut self, len: usize) {
+        self.data.resize(self.head + len, 0);
+        if !self.known.is_empty() {
+            self.known.resize(self.head + len, true);
+        }
+    }
+
+    /// Appends known bytes
+    pub(crate) fn extend<I: IntoIterator<Item = u8>>(&mut self, bytes: I) {
+        self.data.extend(bytes);
+        if !self.known.is_empty() {
+            self.known.resize(self.data.len(), true);
+        }
+    }
+
+    /// Drops up to `count` bytes from the front, so that the remaining bytes keep their offsets
+    ///
+    /// The dropped bytes are only removed from memory once they outnumber the remaining ones, so that the
+    /// remaining bytes are moved at most once per as many dropped bytes.
+    pub(crate) fn remove_front(&mut self, count: usize) {
+        let count = min(count, self.len());
+        self.head += count;
+        self.offset = self.offset.saturating_add(count as u64);
+        if self.head > self.len() {
+            self.data.drain(..self.head);
+            if !self.known.is_empty() {
+                self.known.drain(..self.head);
+            }
+            self.head = 0;
+        }
+    }
+
+    /// Moves the region `delta` bytes towards the start of the view
+    pub(crate) fn move_back(&mut self, delta: u64) {
+        self.offset -= delta;
+    }
+}
+
+impl PartialEq for Region {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+            && self.offset == other.offset
+            && self.len() == other.len()
+            && (0..self.len()).all(|i| self.get(i) == other.get(i))
+    }
+}
+
+impl Eq for Region {}
+
+impl Hash for Region {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name.hash(state);
+        self.offset.hash(state);
+        for i in 0..self.len() {
+            self.get(i).hash(state);
+        }
+    }
+}
+
+/// Describes why a set of regions cannot be displayed.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RegionError {
+    /// Two regions share at least one byte.
+    Overlap {
+        /// Name of the region with the lower offset
+        first: String,
+        /// Name of the region which starts inside of `first`
+        second: String,
+    },
+    /// A region does not fit into the 64 bit address space.
+    OutOfRange {
+        /// Name of the offending region
+        name: String,
+    },
+}
+
+impl fmt::Display for RegionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Overlap { first, second } => write!(f, "region '{second}' overlaps region '{first}'"),
+            Self::OutOfRange { name } => write!(f, "region '{name}' exceeds the 64 bit address space"),
+        }
+    }
+}
+
+impl Error for RegionError {}
+
+/// Sorts the regions by offset and checks that they neither overlap nor exceed the address space.
+///
+/// Empty regions are dropped, unless all regions are empty, in which case the first one is kept, so
+/// there is always at least one region to grow.
+pub(crate) fn normalize_regions(mut regions: Vec<Region>) -> Result<Vec<Region>, RegionError> {
+    for region in &regions {
+        if let Some(len) = (region.len() as u64).checked_sub(1) {
+            if region.offset.checked_add(len).is_none() {
+                return Err(RegionError::OutOfRange {
+                    name: region.name.clone(),
+                });
+            }
+        }
+    }
+
+    regions.sort_by_key(|r| r.offset);
+    if regions.iter().any(|r| !r.is_empty()) {
+        regions.retain(|r| !r.is_empty());
+    } else {
+        regions.truncate(1);
+    }
+    if regions.is_empty() {
+        regions.push(Region::new("", 0, Vec::<u8>::new()));
+    }
+
+    for pair in regions.windows(2) {
+        if pair[0].end() > pair[1].offset {
+            return Err(RegionError::Overlap {
+                first: pair[0].name.clone(),
+                second: pair[1].name.clone(),
+            });
+        }
+    }
+
+    Ok(regions)
+}
+
+/// A displayed row of a `HexView`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Row {
+    /// A line of data, given by its number in the address space (offset / `bytes_per_line`)
+    Line(u64),
+    /// A separator which replaces all the unmapped lines in front of the region with the given index
+    Gap(usize),
+}
+
+/// A run of consecutive lines which contain at least one mapped byte each
+#[derive(Debug, Clone, Copy)]
+struct Segment {
+    first_row: usize,
+    first_line: u64,
+    lines: usize,
+    first_region: usize,
+}
+
+/// Maps the rows of a `HexView` to lines in the address space and back.
+#[derive(Debug, Clone)]
+pub(crate) struct RowMap {
+    segments: Vec<Segment>,
+}
+
+impl RowMap {
+    pub(crate) fn new(regions: &[Region], bytes_per_line: usize) -> Self {
+        let bpl = bytes_per_line as u64;
+        let mut segments: Vec<Segment> = Vec::new();
+        let mut last_line: u64 = 0;
+
+        for (idx, region) in regions.iter().enumerate() {
+            let first = region.offset / bpl;
+            let last = region.last().map_or(first, |l| l / bpl);
+            match segments.last_mut() {
+                Some(seg) if first <= last_line.saturating_add(1) => {
+                    last_line = max(last_line, last);
+                    seg.lines = (last_line - seg.first_line) as usize + 1;
+                }
+                prev => {
+                    let first_row = prev.map_or(0, |s| s.first_row + s.lines + 1);
+                    segments.push(Segment {
+                        first_row,
+                        first_line: first,
+                        lines: (last - first) as usize + 1,
+                        first_region: idx,
+                    });
+                    last_line = last;
+                }
+            }
+        }
+
+        Self { segments }
+    }
+
+    /// Returns the number of rows, including the separators
+    pub(crate) fn len(&self) -> usize {
+        self.segments.last().map_or(1, |s| s.first_row + s.lines)
+    }
+
+    /// Returns the row in which `line` is displayed, or `None` if the line is hidden behind a separator
+    pub(crate) fn row_of_line(&self, line: u64) -> Option<usize> {
+        let idx = self.segments.partition_point(|s| s.first_line <= line).checked_sub(1)?;
+        let seg = &self.segments[idx];
+        let rel = line - seg.first_line;
+        (rel < seg.lines as u64).then(|| seg.first_row + rel as usize)
+    }
+
+    /// Returns the row in which `line` is displayed
+    ///
+    /// If the line is hidden behind a separator, this is the first row after the separator, or the last row if
+    /// there is no such row.
+    pub(crate) fn nearest_row(&self, line: u64) -> usize {
+        self.row_of_line(line).unwrap_or_else(|| {
+            let next = self.segments.partition_point(|s| s.first_line <= line);
+            self.segments.get(next).map_or(self.len() - 1, |s| s.first_row)
+        })
+    }
+
+    /// Returns what is displayed in `row`
+    pub(crate) fn row(&self, row: usize) -> Option<Row> {
+        let idx = self.segments.partition_point(|s| s.first_row <= row).checked_sub(1)?;
+        let seg = &self.segments[idx];
+        if row - seg.first_row < seg.lines {
+            Some(Row::Line(seg.first_line + (row - seg.first_row) as u64))
+        } else {
+            self.segments.get(idx + 1).map(|next| Row::Gap(next.first_region))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remove_front_keeps_bytes_and_offsets() {
+        let mut region = Region::new_partial("ring", 0, (0..10u8).map(|b| (b != 7).then_some(b)));
+        region.remove_front(3);
+        assert_eq!(region.offset(), 3);
+        assert_eq!(region.data(), &[3, 4, 5, 6, 0, 8, 9]);
+        assert!(!region.is_known(4));
+
+        region.set(0, 0x33);
+        region.insert(1, 0x44);
+        region.extend(vec![10, 11]);
+        assert_eq!(region.data(), &[0x33, 0x44, 4, 5, 6, 0, 8, 9, 10, 11]);
+
+        // dropping more bytes than remain compacts the buffer
+        region.remove_front(6);
+        assert_eq!(region.head, 0);
+        assert_eq!(region.offset(), 9);
+        assert_eq!(region.data(), &[8, 9, 10, 11]);
+        assert_eq!(region.get(0), Some(8));
+        assert!(region.is_known(3));
+
+        region.remove_front(10);
+        assert!(region.is_empty());
+        assert_eq!(region.offset(), 13);
+    }
+
+    #[test]
+    fn row_map_hides_unmapped_lines_behind_gaps() {
+        let regions = normalize_regions(vec![
+            Region::new("a", 0, vec![0; 20]),
+            Region::new("b", 0x30, vec![0; 16]),
+            Region::new("c", 0x40, vec![0; 8]),
+            Region::new("d", u64::MAX - 3, vec![0; 4]),
+        ])
+        .unwrap();
+        let map = RowMap::new(&regions, 16);
+        let last_line = u64::MAX / 16;
+
+        let rows: Vec<_> = (0..map.len()).map(|row| map.row(row).unwrap()).collect();
+        assert_eq!(
+            rows,
+            [
+                Row::Line(0),
+                Row::Line(1),
+                Row::Gap(1),
+                Row::Line(3),
+                Row::Line(4),
+                Row::Gap(3),
+                Row::Line(last_line),
+            ]
+        );
+        assert_eq!(map.row(map.len()), None);
+
+        assert_eq!(map.row_of_line(4), Some(4));
+        assert_eq!(map.row_of_line(2), None);
+        assert_eq!(map.nearest_row(2), 3);
+        assert_eq!(map.nearest_row(last_line - 1), 6);
+    }
+
+    #[test]
+    fn row_map_of_empty_view() {
+        let map = RowMap::new(&normalize_regions(Vec::new()).unwrap(), 16);
+        assert_eq!(map.len(), 1);
+        assert_eq!(map.row(0), Some(Row::Line(0)));
+        assert_eq!(map.nearest_row(5), 0);
+    }
+}
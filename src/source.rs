@@ -0,0 +1,473 @@
+use std::cmp::{max, min};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io;
+use std::ops::Range;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use cursive::With;
+
+use history::Edit;
+use region::Row;
+use HexView;
+
+/// How long a byte stays highlighted after a poll found it changed
+const FLASH_DURATION: Duration = Duration::from_secs(1);
+
+/// The poll interval of a new view
+pub(crate) const DEFAULT_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// A source of bytes which may change at any time, like the memory of a running device.
+///
+/// A [`HexView`] with a source [polls](struct.HexView.html#method.set_source) it for the bytes which are currently
+/// visible, so reads should be cheap, but do not need to be fast enough for the whole address space.
+///
+/// [`HexView`]: struct.HexView.html
+pub trait DataSource: Send + Sync {
+    /// Reads `len` bytes, starting at the absolute address `addr`.
+    ///
+    /// Bytes which cannot be read are `None`. If the result is shorter than `len`, the missing bytes at the end
+    /// cannot be read either.
+    fn read(&mut self, addr: u64, len: usize) -> Vec<Option<u8>>;
+
+    /// Writes `data` to the absolute address `addr`, which is how edits of a view reach the source.
+    ///
+    /// The default implementation refuses all writes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bytes cannot be written, the edit is rejected in this case.
+    fn write(&mut self, addr: u64, data: &[u8]) -> io::Result<()> {
+        let _ = (addr, data);
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "the data source is read-only",
+        ))
+    }
+}
+
+/// The state of the source of a [`HexView`]
+pub(crate) struct Polling {
+    source: Box<dyn DataSource>,
+    /// the time of the last poll, `None` if the source has not been polled yet
+    last_poll: Option<Instant>,
+}
+
+impl fmt::Debug for Polling {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Polling")
+            .field("last_poll", &self.last_poll)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A [`DataSource`] which simulates memory in-process, e.g. for tests and demos.
+///
+/// Clones share the same memory, so one clone can be handed to a [`HexView`] while the other one changes the
+/// bytes. Addresses which have never been written cannot be read. Edits of a view are written back to readable
+/// addresses, unless the source is [read-only](#method.set_read_only).
+///
+/// [`DataSource`]: trait.DataSource.html
+/// [`HexView`]: struct.HexView.html
+///
+/// # Examples
+///
+/// ```
+/// # use cursive_hexview::{DataSource, SimulatedSource};
+/// let mut source = SimulatedSource::new();
+/// source.store(0x1000, b"AB");
+/// assert_eq!(source.read(0x0FFF, 3), vec![None, Some(b'A'), Some(b'B')]);
+/// assert_eq!(source.bytes_read(), 3);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SimulatedSource {
+    memory: Arc<Mutex<SimulatedMemory>>,
+}
+
+#[derive(Debug, Default)]
+struct SimulatedMemory {
+    bytes: BTreeMap<u64, u8>,
+    bytes_read: usize,
+    read_only: bool,
+}
+
+impl SimulatedSource {
+    /// Creates a source without any readable bytes.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a source with `data` readable at `addr`.
+    #[must_use]
+    pub fn with_data(addr: u64, data: &[u8]) -> Self {
+        Self::new().with(|s| s.store(addr, data))
+    }
+
+    /// Stores `data` at `addr`, which makes these bytes readable.
+    pub fn store(&self, addr: u64, data: &[u8]) {
+        let mut memory = self.lock();
+        for (addr, &byte) in (addr..).zip(data) {
+            memory.bytes.insert(addr, byte);
+        }
+    }
+
+    /// Makes the bytes in `range` unreadable.
+    pub fn unmap(&self, range: Range<u64>) {
+        let mut memory = self.lock();
+        let addrs: Vec<_> = memory.bytes.range(range).map(|(&addr, _)| addr).collect();
+        for addr in addrs {
+            memory.bytes.remove(&addr);
+        }
+    }
+
+    /// Controls whether the source rejects the edits of a view.
+    ///
+    /// Rejected edits change neither the view nor its undo history.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate cursive;
+    /// extern crate cursive_hexview;
+    ///
+    /// use cursive::event::Event;
+    /// use cursive::view::View;
+    /// use cursive_hexview::{Action, DisplayState, HexView, Region, SimulatedSource};
+    ///
+    /// fn main() {
+    ///     let device = SimulatedSource::with_data(0, &[0x11, 0x22]);
+    ///     let mut view = HexView::new_from_regions(vec![Region::new("ram", 0, vec![0; 2])])
+    ///         .unwrap()
+    ///         .display_state(DisplayState::Editable)
+    ///         .with_source(device.clone());
+    ///     view.layout((80, 4).into());
+    ///     view.on_event(Event::Refresh);
+    ///
+    ///     device.set_read_only(true);
+    ///     view.perform(Action::SetNibble(0xA));
+    ///     assert_eq!(view.byte(0), Some(0x11));
+    /// }
+    /// ```
+    pub fn set_read_only(&self, read_only: bool) {
+        self.lock().read_only = read_only;
+    }
+
+    /// Returns the number of bytes which have been requested by all reads so far.
+    #[must_use]
+    pub fn bytes_read(&self) -> usize {
+        self.lock().bytes_read
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, SimulatedMemory> {
+        self.memory.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl DataSource for SimulatedSource {
+    fn read(&mut self, addr: u64, len: usize) -> Vec<Option<u8>> {
+        let mut memory = self.lock();
+        memory.bytes_read += len;
+        (0..len as u64)
+            .map(|i| addr.checked_add(i).and_then(|addr| memory.bytes.get(&addr).copied()))
+            .collect()
+    }
+
+    fn write(&mut self, addr: u64, data: &[u8]) -> io::Result<()> {
+        let mut memory = self.lock();
+        if memory.read_only {
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                "the memory is read-only",
+            ));
+        }
+        let end = addr.checked_add(data.len() as u64);
+        if end.is_none_or(|end| memory.bytes.range(addr..end).count() != data.len()) {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "the memory is not mapped"));
+        }
+        for (addr, &byte) in (addr..).zip(data) {
+            memory.bytes.insert(addr, byte);
+        }
+        Ok(())
+    }
+}
+
+// implements polling a data source for the visible bytes
+impl HexView {
+    /// Lets the view display the bytes of `source`, which is polled periodically.
+    ///
+    /// The regions of the view define which addresses are read, they are relative to the [`start_addr`] like
+    /// always. Until they have been read, and whenever a read fails, the bytes are [unknown](#method.is_known).
+    /// Only the lines on screen are read, at most once every [poll interval](#method.set_poll_interval) when the
+    /// view receives `Event::Refresh`. Bytes which changed since the previous poll are highlighted for a second.
+    /// Edits are [written back](trait.DataSource.html#method.write) to the source, the size of the data cannot
+    /// change.
+    ///
+    /// Cursive only sends `Event::Refresh` if the application refreshes periodically, e.g. by calling `set_fps` or
+    /// `set_autorefresh` on `Cursive`.
+    ///
+    /// [`start_addr`]: struct.HexViewConfig.html#structfield.start_addr
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate cursive;
+    /// extern crate cursive_hexview;
+    ///
+    /// use cursive::event::Event;
+    /// use cursive::view::View;
+    /// use cursive_hexview::{HexView, Region, SimulatedSource};
+    ///
+    /// fn main() {
+    ///     let device = SimulatedSource::with_data(0, &[42; 1024]);
+    ///     let mut view = HexView::new_from_regions(vec![Region::new("ram", 0, vec![0; 1024])]).unwrap();
+    ///     view.set_source(device);
+    ///     assert_eq!(view.byte(0), None);
+    ///
+    ///     view.layout((80, 4).into());
+    ///     view.on_event(Event::Refresh);
+    ///     assert_eq!(view.byte(0), Some(42));
+    /// }
+    /// ```
+    pub fn set_source<S: DataSource + 'static>(&mut self, source: S) {
+        self.polling = Some(Polling {
+            source: Box::new(source),
+            last_poll: None,
+        });
+        self.reset_source_state();
+    }
+
+    /// Lets the view display the bytes of `source`.
+    ///
+    /// Chainable variant of [`set_source`](#method.set_source).
+    #[must_use]
+    pub fn with_source<S: DataSource + 'static>(self, source: S) -> Self {
+        self.with(|v| v.set_source(source))
+    }
+
+    /// Stops polling the source, the view keeps the bytes which have been read so far.
+    pub fn clear_source(&mut self) {
+        self.polling = None;
+        self.changes.clear();
+    }
+
+    /// Checks whether the view polls a [`DataSource`](trait.DataSource.html).
+    #[must_use]
+    pub const fn has_source(&self) -> bool {
+        self.polling.is_some()
+    }
+
+    /// Returns the minimum time between two polls of the source.
+    #[must_use]
+    pub const fn poll_interval(&self) -> Duration {
+        self.poll_interval
+    }
+
+    /// Sets the minimum time between two polls of the source, the default is half a second.
+    pub fn set_poll_interval(&mut self, interval: Duration) {
+        self.poll_interval = interval;
+    }
+
+    /// Reads the visible lines from the source right away, regardless of the poll interval.
+    ///
+    /// Which lines are on screen is known from the last draw, e.g. only a part of the view is visible inside a
+    /// `ScrollView`. Before the first draw, all lines of the last layout are read, before the first layout nothing.
+    pub fn poll_source(&mut self) {
+        let Some(size) = self.size else {
+            return;
+        };
+        let viewport = *self.viewport.lock().unwrap_or_else(PoisonError::into_inner);
+        let (offset, height) = viewport.unwrap_or((0, size.y));
+        let bytes_per_line = self.bytes_per_line() as u64;
+        let rows = self.get_row_map();
+        let first = self.v_scroll.saturating_add(offset);
+        let visible = first..min(rows.len(), first.saturating_add(height));
+        let start_addr = self.config.start_addr;
+        let Some(polling) = self.polling.as_mut() else {
+            return;
+        };
+        let now = Instant::now();
+        polling.last_poll = Some(now);
+        self.changes
+            .retain(|_, changed| now.duration_since(*changed) < FLASH_DURATION);
+
+        for row in visible {
+            let Some(Row::Line(line)) = rows.row(row) else {
+                continue;
+            };
+            let first = line * bytes_per_line;
+            let last = first.saturating_add(bytes_per_line - 1);
+            for region in &mut self.regions {
+                let Some(region_last) = region.last() else {
+                    continue;
+                };
+                if region.offset() > last || region_last < first {
+                    continue;
+                }
+                let from = max(first, region.offset());
+                let to = min(last, region_last);
+                let pos = (from - region.offset()) as usize;
+                let len = (to - from) as usize + 1;
+                let mut bytes = polling.source.read(start_addr.wrapping_add(from), len);
+                bytes.resize(len, None);
+                for (i, byte) in bytes.into_iter().enumerate() {
+                    match byte {
+                        Some(byte) => {
+                            if region.is_known(pos + i) && region.data()[pos + i] != byte {
+                                self.changes.insert(from + i as u64, now);
+                            }
+                            region.set(pos + i, byte);
+                        }
+                        None => region.set_unknown(pos + i),
+                    }
+                }
+            }
+        }
+    }
+
+    /// Checks whether the byte at `addr` changed during one of the polls of the last second.
+    #[must_use]
+    pub fn is_changed(&self, addr: u64) -> bool {
+        self.is_flashing(addr.wrapping_sub(self.config.start_addr))
+    }
+
+    /// Checks whether the byte at `offset` is highlighted as changed
+    pub(crate) fn is_flashing(&self, offset: u64) -> bool {
+        self.changes
+            .get(&offset)
+            .is_some_and(|changed| changed.elapsed() < FLASH_DURATION)
+    }
+
+    /// Polls the source if the poll interval has passed
+    pub(crate) fn poll_source_if_due(&mut self) {
+        let due = self.polling.as_ref().is_some_and(|polling| {
+            polling
+                .last_poll
+                .is_none_or(|last| last.elapsed() >= self.poll_interval)
+        });
+        if due {
+            self.poll_source();
+        }
+    }
+
+    /// Writes the edit back to the source, returns `false` if the source rejects it
+    ///
+    /// The size of a source cannot change, so resizing edits are always rejected.
+    pub(crate) fn write_back(&mut self, edit: Edit) -> bool {
+        let start_addr = self.config.start_addr;
+        match (self.polling.as_mut(), edit) {
+            (None, _) => true,
+            (Some(polling), Edit::Set { offset, new, .. }) => {
+                polling.source.write(start_addr.wrapping_add(offset), &[new]).is_ok()
+            }
+            (Some(_), _) => false,
+        }
+    }
+
+    /// Marks all bytes as unread after the source or the regions changed
+    pub(crate) fn reset_source_state(&mut self) {
+        self.changes.clear();
+        if let Some(polling) = self.polling.as_mut() {
+            polling.last_poll = None;
+            for region in &mut self.regions {
+                region.forget();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate cursive as cursive_full;
+
+    use self::cursive_full::backends::puppet::Backend;
+    use cursive::event::Event;
+    use cursive::view::{Nameable, Resizable, View};
+    use cursive::views::ScrollView;
+    use cursive::Cursive;
+
+    use super::*;
+    use {Action, DisplayState, HexViewConfig, Region};
+
+    fn ram_view(device: &SimulatedSource) -> HexView {
+        HexView::new_from_regions(vec![Region::new("ram", 0, vec![0; 1024])])
+            .unwrap()
+            .with_source(device.clone())
+    }
+
+    #[test]
+    fn layout_does_not_poll() {
+        let device = SimulatedSource::with_data(0, &[0; 1024]);
+        let mut view = ram_view(&device);
+        view.layout((80, 4).into());
+        assert_eq!(device.bytes_read(), 0);
+
+        view.on_event(Event::Refresh);
+        assert_eq!(device.bytes_read(), 4 * 16);
+    }
+
+    #[test]
+    fn scroll_view_polls_lines_on_screen() {
+        let device = SimulatedSource::with_data(0, &[0; 1024]);
+        let mut cur = Cursive::new().into_runner(Backend::init(Some((80, 6).into())));
+        cur.add_fullscreen_layer(ScrollView::new(ram_view(&device).with_name("hex")).full_screen());
+        cur.refresh();
+        assert_eq!(
+            cur.call_on_name("hex", |v: &mut HexView| v.size.map(|s| s.y)),
+            Some(Some(64))
+        );
+
+        cur.on_event(Event::Refresh);
+        assert_eq!(device.bytes_read(), 6 * 16);
+    }
+
+    #[test]
+    fn rejected_edits_keep_history() {
+        let mut device = SimulatedSource::with_data(0, &[0x11, 0x22]);
+        let mut view = HexView::new_from_regions(vec![Region::new("ram", 0, vec![0; 2])])
+            .unwrap()
+            .display_state(DisplayState::Editable)
+            .with_source(device.clone());
+        view.layout((80, 4).into());
+        view.on_event(Event::Refresh);
+
+        view.perform(Action::SetNibble(0xA));
+        assert_eq!(device.read(0, 1), [Some(0xA1)]);
+
+        device.set_read_only(true);
+        assert!(!view.undo());
+        assert_eq!(view.byte(0), Some(0xA1));
+
+        // the byte cannot be read anymore, so undoing must not overwrite it
+        device.set_read_only(false);
+        device.unmap(0..1);
+        view.poll_source();
+        device.store(0, &[0x33]);
+        assert!(!view.undo());
+        assert_eq!(device.read(0, 1), [Some(0x33)]);
+        assert!(view.can_undo());
+    }
+
+    #[test]
+    fn changes_are_detected_relative_to_start_addr() {
+        let device = SimulatedSource::with_data(0x2000_0000, &[0; 1024]);
+        let mut view = ram_view(&device).with_config(HexViewConfig {
+            start_addr: 0x2000_0000,
+            ..Default::default()
+        });
+        view.set_cursor_addr(0x2000_0001);
+        assert_eq!(view.status().byte, None);
+
+        view.layout((80, 4).into());
+        view.on_event(Event::Refresh);
+        assert_eq!(view.status().byte, Some(0));
+        assert!(!view.is_changed(0x2000_0001));
+
+        device.store(0x2000_0001, &[42]);
+        view.poll_source();
+        assert_eq!(view.status().byte, Some(42));
+        assert!(view.is_changed(0x2000_0001));
+        assert!(!view.is_changed(0x2000_0000));
+    }
+}
@@ -0,0 +1,243 @@
+use std::io::{self, Write};
+
+use parse::{build_view, Chunk, ParseError, ParseErrorKind, Record};
+use HexView;
+
+/// Number of data bytes written per record
+const BYTES_PER_RECORD: usize = 16;
+
+/// Writes a single record of `kind` ('0'-'9'), including count and checksum
+fn write_record<W: Write>(writer: &mut W, kind: char, addr: &[u8], data: &[u8]) -> io::Result<()> {
+    let mut record = vec![(addr.len() + data.len() + 1) as u8];
+    record.extend_from_slice(addr);
+    record.extend_from_slice(data);
+    let checksum = !record.iter().fold(0u8, |acc, b| acc.wrapping_add(*b));
+
+    write!(writer, "S{kind}")?;
+    for b in record.iter().chain(Some(&checksum)) {
+        write!(writer, "{b:02X}")?;
+    }
+    writeln!(writer)
+}
+
+impl HexView {
+    /// Creates a new `HexView` from the content of a Motorola S-record file.
+    ///
+    /// The lowest address found in the file becomes the `start_addr` of the view. Data which is not
+    /// contiguous is split into several regions, see [`set_regions`](#method.set_regions).
+    /// Header (`S0`) and count records (`S5`, `S6`) are ignored and everything after a termination record
+    /// (`S7`, `S8`, `S9`) is not parsed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cursive_hexview::HexView;
+    /// let view = HexView::new_from_srec("S0030000FC\nS107100001020304DE\nS9030000FC\n").unwrap();
+    /// assert_eq!(view.config().start_addr, 0x1000);
+    /// assert_eq!(view.data(), &[1, 2, 3, 4]);
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error with the position of the first malformed record, a record with a wrong checksum or
+    /// data which is defined twice.
+    pub fn new_from_srec(text: &str) -> Result<Self, ParseError> {
+        let mut chunks = Vec::new();
+
+        for (idx, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let mut record = Record::new(idx + 1, line, 'S')?;
+            let kind = record.take_chars(1)?;
+            let addr_len = match kind {
+                "0" | "1" | "5" | "9" => 2,
+                "2" | "6" | "8" => 3,
+                "3" | "7" => 4,
+                _ => return Err(record.error_at(1, ParseErrorKind::UnsupportedRecord(format!("S{kind}")))),
+            };
+
+            let bytes = record.bytes()?;
+            if bytes.len() < addr_len + 2 {
+                return Err(record.error_at(line.len(), ParseErrorKind::UnexpectedEndOfLine));
+            }
+            let count = bytes[0] as usize;
+            if bytes.len() != count + 1 {
+                return Err(record.error_at_byte(
+                    0,
+                    ParseErrorKind::LengthMismatch {
+                        expected: count,
+                        found: bytes.len() - 1,
+                    },
+                ));
+            }
+            let checksum = bytes[count];
+            let expected = !bytes[..count].iter().fold(0u8, |acc, b| acc.wrapping_add(*b));
+            if checksum != expected {
+                return Err(record.error_at_byte(
+                    count,
+                    ParseErrorKind::ChecksumMismatch {
+                        expected,
+                        found: checksum,
+                    },
+                ));
+            }
+
+            let addr = bytes[1..=addr_len].iter().fold(0u64, |acc, b| acc << 8 | u64::from(*b));
+            match kind {
+                "1" | "2" | "3" => chunks.push(Chunk {
+                    addr,
+                    line: idx + 1,
+                    data: bytes[addr_len + 1..count].to_vec(),
+                }),
+                "7" | "8" | "9" => break,
+                _ => {}
+            }
+        }
+
+        build_view(chunks)
+    }
+
+    /// Writes the current data of this view as Motorola S-records.
+    ///
+    /// The smallest address width which fits all regions is used (`S1`, `S2` or `S3` records). Unknown bytes are
+    /// left out, like unmapped ones. The output starts with an empty header record, and ends with a count record
+    /// and a termination record.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cursive_hexview::HexView;
+    /// let view = HexView::new_from_srec("S107100001020304DE\n").unwrap();
+    /// let mut out = Vec::new();
+    /// view.write_srec(&mut out).unwrap();
+    /// assert_eq!(out, b"S0030000FC\nS107100001020304DE\nS5030001FB\nS9030000FC\n");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`io::ErrorKind::InvalidInput`] if any address exceeds 32 bits, or if writing fails.
+    pub fn write_srec<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let start = self.config().start_addr;
+        let end = self
+            .regions()
+            .iter()
+            .filter(|r| !r.is_empty())
+            .map(|r| start.checked_add(r.end() - 1))
+            .try_fold(0, |max, end| end.map(|e| max.max(e)))
+            .filter(|end| *end <= u64::from(u32::MAX))
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "S-records only support 32 bit addresses"))?;
+        let (data_kind, end_kind, addr_len) = match end {
+            0..=0xFFFF => ('1', '9', 2),
+            0x1_0000..=0xFF_FFFF => ('2', '8', 3),
+            _ => ('3', '7', 4),
+        };
+
+        write_record(&mut writer, '0', &[0, 0], &[])?;
+        let mut count = 0u32;
+        for region in self.regions() {
+            for (pos, run) in region.known_runs() {
+                let addr = start + region.offset() + pos as u64;
+                for (i, data) in run.chunks(BYTES_PER_RECORD).enumerate() {
+                    let addr = (addr + (i * BYTES_PER_RECORD) as u64).to_be_bytes();
+                    write_record(&mut writer, data_kind, &addr[8 - addr_len..], data)?;
+                    count += 1;
+                }
+            }
+        }
+        if count <= 0xFFFF {
+            write_record(&mut writer, '5', &count.to_be_bytes()[2..], &[])?;
+        } else if count <= 0xFF_FFFF {
+            write_record(&mut writer, '6', &count.to_be_bytes()[1..], &[])?;
+        }
+        write_record(&mut writer, end_kind, &vec![0; addr_len], &[])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Region;
+
+    fn error(text: &str) -> (usize, usize, ParseErrorKind) {
+        let err = HexView::new_from_srec(text).unwrap_err();
+        (err.line(), err.column(), err.kind().clone())
+    }
+
+    fn write(start_addr: u64, data: &[u8]) -> String {
+        let mut view = HexView::new_from_iter(data);
+        view.config_mut().start_addr = start_addr;
+        let mut out = Vec::new();
+        view.write_srec(&mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn reports_malformed_records() {
+        assert_eq!(error("107100001020304DE\n"), (1, 1, ParseErrorKind::InvalidStartCode));
+        assert_eq!(
+            error("S4030000FC\n"),
+            (1, 2, ParseErrorKind::UnsupportedRecord("S4".into()))
+        );
+        assert_eq!(
+            error("S107100001020304DF\n"),
+            (
+                1,
+                17,
+                ParseErrorKind::ChecksumMismatch {
+                    expected: 0xDE,
+                    found: 0xDF
+                }
+            )
+        );
+        assert_eq!(
+            error("\nS1071000010203DE\n"),
+            (2, 3, ParseErrorKind::LengthMismatch { expected: 7, found: 6 })
+        );
+        assert_eq!(error("S101\n"), (1, 5, ParseErrorKind::UnexpectedEndOfLine));
+        assert_eq!(
+            error("S107100001020304DE\nS107100001020304DE\n"),
+            (2, 1, ParseErrorKind::OverlappingData(0x1000))
+        );
+    }
+
+    #[test]
+    fn stops_at_termination_record() {
+        let view = HexView::new_from_srec("S107100001020304DE\nS9030000FC\nnot parsed\n").unwrap();
+        assert_eq!(view.data(), &[1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn uses_smallest_address_width() {
+        for (start_addr, kind) in [(0xFFFF, "S2"), (0xFF_FFFF, "S3")] {
+            let text = write(start_addr, &[1, 2]);
+            assert!(text.lines().nth(1).unwrap().starts_with(kind));
+
+            let view = HexView::new_from_srec(&text).unwrap();
+            assert_eq!(view.config().start_addr, start_addr);
+            assert_eq!(view.data(), &[1, 2]);
+        }
+    }
+
+    #[test]
+    fn rejects_addresses_above_32_bits() {
+        let mut view = HexView::new_from_iter([0; 4]);
+        view.config_mut().start_addr = 0xFFFF_FFFE;
+        let err = view.write_srec(Vec::new()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn leaves_out_unknown_bytes() {
+        let regions = vec![Region::new_partial("flash", 0, vec![Some(1), None, Some(3), Some(4)])];
+        let view = HexView::new_from_regions(regions).unwrap();
+        let mut out = Vec::new();
+        view.write_srec(&mut out).unwrap();
+        assert_eq!(
+            out,
+            b"S0030000FC\nS104000001FA\nS10500020304F1\nS5030002FA\nS9030000FC\n"
+        );
+    }
+}
@@ -0,0 +1,124 @@
+use std::fmt;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use cursive::view::View;
+use cursive::{Printer, Vec2};
+use unicode_width::UnicodeWidthStr;
+
+use make_printable;
+
+/// A snapshot of the cursor and edit state of a [`HexView`].
+///
+/// Its `Display` implementation renders the line shown by a [`HexStatusView`].
+///
+/// [`HexView`]: struct.HexView.html
+/// [`HexStatusView`]: struct.HexStatusView.html
+///
+/// # Examples
+///
+/// ```
+/// # use cursive_hexview::HexView;
+/// let mut view = HexView::new_from_iter(b"ABC");
+/// view.set_cursor_addr(1);
+///
+/// let status = view.status();
+/// assert_eq!(status.byte, Some(b'B'));
+/// assert_eq!(
+///     status.to_string(),
+///     "Addr 0x1 (1) │ Byte 0x42 66 0o102 0b01000010 'B' │ Sel 0 │ OVR │ Saved │ Len 3"
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct HexStatus {
+    /// The address of the byte under the cursor
+    pub addr: u64,
+    /// The number of hex digits of the addresses in the view
+    pub addr_width: usize,
+    /// The byte under the cursor, `None` if it is not mapped
+    pub byte: Option<u8>,
+    /// The number of selected bytes, saturating at `u64::MAX` for a selection of the whole address space
+    pub selection_len: u64,
+    /// Whether typed hex digits insert new bytes
+    pub insert_mode: bool,
+    /// Whether the data has been edited since it was last saved
+    pub dirty: bool,
+    /// The number of bytes in the view
+    pub len: usize,
+}
+
+impl fmt::Display for HexStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Addr 0x{:0width$X} ({}) │ ",
+            self.addr,
+            self.addr,
+            width = self.addr_width
+        )?;
+        match self.byte {
+            Some(b) => write!(f, "Byte 0x{b:02X} {b} 0o{b:03o} 0b{b:08b} '{}'", make_printable(b))?,
+            None => write!(f, "Byte --")?,
+        }
+        write!(
+            f,
+            " │ Sel {} │ {} │ {} │ Len {}",
+            self.selection_len,
+            if self.insert_mode { "INS" } else { "OVR" },
+            if self.dirty { "Modified" } else { "Saved" },
+            self.len
+        )
+    }
+}
+
+/// A single line status bar, which shows the [`HexStatus`] of a [`HexView`].
+///
+/// It is created by [`HexView::status_view`] and follows the view after every event and layout, so it can be
+/// placed anywhere in the same cursive tree.
+///
+/// [`HexStatus`]: struct.HexStatus.html
+/// [`HexView`]: struct.HexView.html
+/// [`HexView::status_view`]: struct.HexView.html#method.status_view
+///
+/// # Examples
+///
+/// ```
+/// extern crate cursive;
+/// extern crate cursive_hexview;
+///
+/// use cursive::views::LinearLayout;
+/// use cursive_hexview::{DisplayState, HexView};
+///
+/// fn main() {
+///     let view = HexView::new_from_iter(b"Hello, World!").display_state(DisplayState::Editable);
+///     let status = view.status_view();
+///
+///     let mut cur = cursive::dummy();
+///     cur.add_fullscreen_layer(LinearLayout::vertical().child(view).child(status));
+/// }
+/// ```
+#[derive(Debug)]
+pub struct HexStatusView {
+    status: Arc<Mutex<HexStatus>>,
+}
+
+impl HexStatusView {
+    pub(crate) fn new(status: Arc<Mutex<HexStatus>>) -> Self {
+        Self { status }
+    }
+
+    /// Returns the status which is currently displayed.
+    #[must_use]
+    pub fn status(&self) -> HexStatus {
+        *self.status.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl View for HexStatusView {
+    fn draw(&self, printer: &Printer) {
+        printer.print((0, 0), &self.status().to_string());
+    }
+
+    fn required_size(&mut self, _constraint: Vec2) -> Vec2 {
+        (self.status().to_string().width(), 1).into()
+    }
+}
@@ -0,0 +1,286 @@
+use std::borrow::Borrow;
+use std::cmp::{max, min};
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use cursive::CbSink;
+
+use {HexEditorView, HexView};
+
+// implements appending data to a view, e.g. while it arrives from a stream
+impl HexView {
+    /// Appends `data` to the end of the last region.
+    ///
+    /// In [follow mode](#method.set_follow), the cursor moves to the new last byte. If a
+    /// [maximum length](#method.set_max_len) is set, the oldest bytes are dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cursive_hexview::HexView;
+    /// let mut view = HexView::new_from_iter(b"Hello");
+    /// view.append(b", World!");
+    /// assert_eq!(view.data(), b"Hello, World!");
+    /// ```
+    pub fn append<B: Borrow<u8>, I: IntoIterator<Item = B>>(&mut self, data: I) {
+        let last = self.regions.last_mut().expect("there is always one region");
+        let old_len = last.len();
+        let max_len = (u64::MAX - last.offset()).saturating_add(1);
+        let room = min(max_len, usize::MAX as u64) as usize - old_len;
+        last.extend(data.into_iter().take(room).map(|b| *b.borrow()));
+        if last.len() == old_len {
+            return;
+        }
+
+        self.layout_changed = true;
+        self.trim_to_max_len();
+        if self.follow {
+            self.move_cursor_to_end();
+        }
+    }
+
+    /// Checks whether the view follows the newest data, see [`set_follow`](#method.set_follow).
+    #[must_use]
+    pub const fn is_following(&self) -> bool {
+        self.follow
+    }
+
+    /// Controls whether the cursor jumps to the last byte whenever data is [appended](#method.append).
+    ///
+    /// Enabling it moves the cursor to the last byte immediately, so the view scrolls to the newest data like
+    /// `tail -f` does.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cursive_hexview::HexView;
+    /// let mut view = HexView::new_from_iter(b"Hello");
+    /// view.set_follow(true);
+    /// view.append(b", World!");
+    /// assert_eq!(view.cursor_addr(), 12);
+    /// ```
+    pub fn set_follow(&mut self, follow: bool) {
+        self.follow = follow;
+        if follow {
+            self.move_cursor_to_end();
+        }
+    }
+
+    /// Returns the maximum number of bytes the view keeps, see [`set_max_len`](#method.set_max_len).
+    #[must_use]
+    pub const fn max_len(&self) -> Option<usize> {
+        self.max_len
+    }
+
+    /// Limits the number of bytes the view keeps, `None` removes the limit.
+    ///
+    /// Whenever the data grows beyond the limit, the oldest bytes are dropped like in a ring buffer. The remaining
+    /// bytes keep their addresses, the [`start_addr`] advances by whole lines instead, so the first line may
+    /// start with unmapped bytes. As the offsets of all bytes change, the undo history is cleared.
+    ///
+    /// [`start_addr`]: struct.HexViewConfig.html#structfield.start_addr
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use cursive_hexview::HexView;
+    /// let mut view = HexView::new();
+    /// view.set_max_len(Some(20));
+    /// view.append(0..40u8);
+    ///
+    /// assert_eq!(view.len(), 20);
+    /// assert_eq!(view.config().start_addr, 16);
+    /// assert_eq!(view.regions()[0].offset(), 4);
+    /// assert_eq!(view.regions()[0].data()[0], 20);
+    /// ```
+    pub fn set_max_len(&mut self, max_len: Option<usize>) {
+        self.max_len = max_len;
+        self.trim_to_max_len();
+    }
+
+    /// Moves the cursor to the last mapped byte
+    fn move_cursor_to_end(&mut self) {
+        let last = self.regions.iter().rev().find_map(|r| r.last()).unwrap_or(0);
+        self.move_cursor_to(last);
+    }
+
+    /// Drops the oldest bytes beyond `max_len` and moves the start address by the number of dropped lines
+    fn trim_to_max_len(&mut self) {
+        let Some(max_len) = self.max_len else {
+            return;
+        };
+        let mut excess = self.len().saturating_sub(max_len);
+        if excess == 0 {
+            return;
+        }
+
+        while excess > 0 {
+            let count = min(excess, self.regions[0].len());
+            excess -= count;
+            if count == self.regions[0].len() && self.regions.len() > 1 {
+                self.regions.remove(0);
+            } else {
+                self.regions[0].remove_front(count);
+            }
+        }
+
+        let first = self.regions[0].offset();
+        let bytes_per_line = self.bytes_per_line() as u64;
+        let shift = first / bytes_per_line * bytes_per_line;
+        for region in &mut self.regions {
+            region.move_back(shift);
+        }
+        self.config.start_addr = self.config.start_addr.saturating_add(shift);
+        self.cursor.offset = self.cursor.offset.saturating_sub(shift);
+        self.selection = self
+            .selection
+            .filter(|(_, last)| *last >= first)
+            .map(|(start, last)| (max(start, first) - shift, last - shift));
+        self.changes = self
+            .changes
+            .drain()
+            .filter(|(offset, _)| *offset >= first)
+            .map(|(offset, time)| (offset - shift, time))
+            .collect();
+        self.drag_start = self.drag_start.filter(|o| *o >= first).map(|o| o - shift);
+        self.last_click = self
+            .last_click
+            .filter(|(_, o)| *o >= first)
+            .map(|(t, o)| (t, o - shift));
+        self.v_scroll = self.v_scroll.saturating_sub((shift / bytes_per_line) as usize);
+        let dirty = self.history.is_dirty();
+        self.history.clear(dirty);
+        self.layout_changed = true;
+        self.clamp_cursor();
+    }
+}
+
+/// Appends data to a named [`HexView`] or [`HexEditorView`] from any thread.
+///
+/// The data is sent through the callback sink of cursive, so it is appended by the thread which runs the event
+/// loop. The view has to be wrapped in a `NamedView` with the given name. Data for which no such view is found is
+/// counted by [`dropped`](#method.dropped).
+///
+/// [`HexView`]: struct.HexView.html
+/// [`HexEditorView`]: struct.HexEditorView.html
+///
+/// # Examples
+///
+/// ```
+/// extern crate cursive;
+/// extern crate cursive_hexview;
+///
+/// use cursive::view::Nameable;
+/// use cursive_hexview::{DataSender, HexView};
+/// use std::thread;
+///
+/// fn main() {
+///     let backend = cursive::backends::puppet::Backend::init(None);
+///     let mut cur = cursive::Cursive::new().into_runner(backend);
+///     cur.add_layer(HexView::new().with_name("serial"));
+///
+///     let sender = DataSender::new(cur.cb_sink().clone(), "serial");
+///     let thread_sender = sender.clone();
+///     thread::spawn(move || thread_sender.send(b"Hello".to_vec())).join().unwrap();
+///
+///     cur.step();
+///     let len = cur.call_on_name("serial", |v: &mut HexView| v.len());
+///     assert_eq!(len, Some(5));
+///     assert_eq!(sender.dropped(), 0);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct DataSender {
+    sink: CbSink,
+    name: String,
+    /// the number of bytes for which no view was found, shared by all clones
+    dropped: Arc<AtomicUsize>,
+}
+
+impl DataSender {
+    /// Creates a sender which appends to the view called `name`, using the callback sink `sink` of cursive.
+    pub fn new<S: Into<String>>(sink: CbSink, name: S) -> Self {
+        Self {
+            sink,
+            name: name.into(),
+            dropped: Arc::default(),
+        }
+    }
+
+    /// Queues `data` to be appended to the view.
+    ///
+    /// Returns `false` if cursive is no longer running. Data for a view which cannot be found is dropped and
+    /// counted by [`dropped`](#method.dropped).
+    pub fn send<D: Into<Vec<u8>>>(&self, data: D) -> bool {
+        let name = self.name.clone();
+        let data = data.into();
+        let dropped = Arc::clone(&self.dropped);
+        self.sink
+            .send(Box::new(move |s| {
+                let found = s.call_on_name(&name, |view: &mut HexView| view.append(&data)).is_some()
+                    || s.call_on_name(&name, |editor: &mut HexEditorView| editor.append(&data))
+                        .is_some();
+                if !found {
+                    dropped.fetch_add(data.len(), Ordering::Relaxed);
+                }
+            }))
+            .is_ok()
+    }
+
+    /// Returns the number of bytes which were dropped by all clones of this sender, because the view was not
+    /// found.
+    #[must_use]
+    pub fn dropped(&self) -> usize {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate cursive as cursive_full;
+
+    use std::time::Instant;
+
+    use self::cursive_full::backends::puppet::Backend;
+    use cursive::view::Nameable;
+    use cursive::Cursive;
+
+    use super::*;
+
+    #[test]
+    fn sender_appends_to_editor() {
+        let mut cur = Cursive::new().into_runner(Backend::init(None));
+        cur.add_layer(HexEditorView::new(HexView::new()).with_name("editor"));
+        let editor = DataSender::new(cur.cb_sink().clone(), "editor");
+        let missing = DataSender::new(cur.cb_sink().clone(), "missing");
+
+        assert!(editor.send(b"Hello".to_vec()));
+        assert!(missing.send(b"lost".to_vec()));
+        cur.step();
+
+        let len = cur.call_on_name("editor", |e: &mut HexEditorView| e.hex_view().len());
+        assert_eq!(len, Some(5));
+        assert_eq!(editor.dropped(), 0);
+        assert_eq!(missing.dropped(), 4);
+    }
+
+    #[test]
+    fn trimming_shifts_offsets() {
+        let mut view = HexView::new_from_iter(0..32u8);
+        let now = Instant::now();
+        view.set_selection(2..=20);
+        view.changes.insert(1, now);
+        view.changes.insert(18, now);
+        view.drag_start = Some(18);
+        view.last_click = Some((now, 2));
+
+        view.set_max_len(Some(16));
+        assert_eq!(view.config().start_addr, 16);
+        // the selection is clamped to the first remaining byte
+        assert_eq!(view.selection(), Some(16..=20));
+        assert_eq!(view.changes.keys().collect::<Vec<_>>(), [&2]);
+        assert_eq!(view.drag_start, Some(2));
+        assert_eq!(view.last_click, None);
+    }
+}